@@ -0,0 +1,63 @@
+extern crate lib;
+
+use lib::types::linalg::dimension::Dimension;
+use lib::types::linalg::matrix::Matrix;
+use std::time::{Duration, Instant};
+
+pub const SIZES: [usize; 9] = [4, 8, 16, 32, 64, 128, 256, 512, 1024];
+
+//Reference triple loop the blocked kernel is compared against
+fn naive_mul(m1: &Matrix<f32>, m2: &Matrix<f32>) -> Matrix<f32> {
+    let dimension = Dimension::new(m1.dimension.rows, m2.dimension.columns);
+    Matrix::from_closure(
+        |index| {
+            let (row, column) = dimension.to_xy(index);
+            (0..m1.dimension.columns).fold(0., |curr, i| curr + m1[(row, i)] * m2[(i, column)])
+        },
+        dimension,
+    )
+}
+
+//Sums one element of every product so the multiplications can't be optimized away
+fn time<F: FnMut() -> Matrix<f32>>(iterations: usize, mut f: F) -> (Duration, f32) {
+    let start = Instant::now();
+    let mut checksum = 0.;
+    for _ in 0..iterations {
+        checksum += f().data[0];
+    }
+    (start.elapsed() / iterations as u32, checksum)
+}
+
+fn main() {
+    println!(
+        "{:>6} {:>14} {:>14} {:>14}",
+        "size", "naive", "blocked", "parallel"
+    );
+    for &size in SIZES.iter() {
+        let dimension = Dimension::new(size, size);
+        let m1 = Matrix::from_closure(|i| (i % 17) as f32 * 0.25, dimension);
+        let m2 = Matrix::from_closure(|i| (i % 11) as f32 - 5., dimension);
+        //Aim for roughly 2^28 multiply-adds per measurement
+        let iterations = ((1usize << 28) / (size * size * size)).max(1);
+
+        let (naive, naive_checksum) = time(iterations, || naive_mul(&m1, &m2));
+        let (blocked, blocked_checksum) = time(iterations, || m1.checked_mul(&m2).unwrap());
+        assert_eq!(naive_checksum, blocked_checksum);
+        #[cfg(feature = "parallel")]
+        let parallel = {
+            let (parallel, parallel_checksum) =
+                time(iterations, || m1.checked_par_mul(&m2).unwrap());
+            assert_eq!(naive_checksum, parallel_checksum);
+            format!("{:?}", parallel)
+        };
+        #[cfg(not(feature = "parallel"))]
+        let parallel = "-".to_owned();
+        println!(
+            "{:>6} {:>14} {:>14} {:>14}",
+            size,
+            format!("{:?}", naive),
+            format!("{:?}", blocked),
+            parallel
+        );
+    }
+}
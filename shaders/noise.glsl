@@ -0,0 +1,289 @@
+//Noise functions matching lib::types::noise operation for operation, so CPU and GPU agree.
+//Has no #version line, splice it into a shader with noise::glsl::with_noise.
+const uint HASH_MULTIPLIER_1 = 0x7feb352du;
+const uint HASH_MULTIPLIER_2 = 0x846ca68bu;
+const float VALUE_SCALE = 1.192093e-7;
+const float PERLIN2_SCALE = 0.507;
+const float PERLIN3_SCALE = 0.936;
+const float PERLIN4_SCALE = 0.87;
+const float SIMPLEX_F2 = 0.36602542;
+const float SIMPLEX_G2 = 0.21132487;
+const float SIMPLEX_F3 = 0.33333334;
+const float SIMPLEX_G3 = 0.16666667;
+const float SIMPLEX_F4 = 0.309017;
+const float SIMPLEX_G4 = 0.1381966;
+const float SIMPLEX2_SCALE = 40.0;
+const float SIMPLEX3_SCALE = 32.0;
+const float SIMPLEX4_SCALE = 27.0;
+const vec3 WARP_OFFSET_1 = vec3(5.2, 1.3, 7.1);
+const vec3 WARP_OFFSET_2 = vec3(1.7, 9.2, 3.4);
+
+uint hash(uint x) {
+    x ^= x >> 16u;
+    x *= HASH_MULTIPLIER_1;
+    x ^= x >> 15u;
+    x *= HASH_MULTIPLIER_2;
+    x ^= x >> 16u;
+    return x;
+}
+uint hash2(ivec2 i, uint seed) {
+    return hash(hash(hash(seed) ^ uint(i.x)) ^ uint(i.y));
+}
+uint hash3(ivec3 i, uint seed) {
+    return hash(hash2(i.xy, seed) ^ uint(i.z));
+}
+uint hash4(ivec4 i, uint seed) {
+    return hash(hash3(i.xyz, seed) ^ uint(i.w));
+}
+float to_signed_unit(uint h) {
+    return float(h >> 8u) * VALUE_SCALE - 1.0;
+}
+float fade(float t) {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+//mix() may round differently
+float lerp(float t, float a, float b) {
+    return a + t * (b - a);
+}
+
+float grad2(uint h, float x, float y) {
+    h &= 7u;
+    float u = h < 4u ? x : y;
+    float v = h < 4u ? y : x;
+    u = (h & 1u) == 0u ? u : -u;
+    v = (h & 2u) == 0u ? 2.0 * v : -2.0 * v;
+    return u + v;
+}
+float grad3(uint h, float x, float y, float z) {
+    h &= 15u;
+    float u = h < 8u ? x : y;
+    float v = h < 4u ? y : (h == 12u || h == 14u ? x : z);
+    u = (h & 1u) == 0u ? u : -u;
+    v = (h & 2u) == 0u ? v : -v;
+    return u + v;
+}
+float grad4(uint h, float x, float y, float z, float w) {
+    h &= 31u;
+    float u = h < 24u ? x : y;
+    float v = h < 16u ? y : z;
+    float s = h < 8u ? z : w;
+    u = (h & 1u) == 0u ? u : -u;
+    v = (h & 2u) == 0u ? v : -v;
+    s = (h & 4u) == 0u ? s : -s;
+    return u + v + s;
+}
+
+float perlin2(vec2 p, uint seed) {
+    vec2 f = floor(p);
+    ivec2 i = ivec2(f);
+    float x = p.x - f.x;
+    float y = p.y - f.y;
+    float u = fade(x);
+    float v = fade(y);
+    float n00 = grad2(hash2(i, seed), x, y);
+    float n10 = grad2(hash2(i + ivec2(1, 0), seed), x - 1.0, y);
+    float n01 = grad2(hash2(i + ivec2(0, 1), seed), x, y - 1.0);
+    float n11 = grad2(hash2(i + ivec2(1, 1), seed), x - 1.0, y - 1.0);
+    return PERLIN2_SCALE * lerp(v, lerp(u, n00, n10), lerp(u, n01, n11));
+}
+float perlin3_corner(ivec3 i, vec3 p, ivec3 d, uint seed) {
+    vec3 o = p - vec3(d);
+    return grad3(hash3(i + d, seed), o.x, o.y, o.z);
+}
+float perlin3(vec3 p, uint seed) {
+    vec3 f = floor(p);
+    ivec3 i = ivec3(f);
+    vec3 x = p - f;
+    float u = fade(x.x);
+    float v = fade(x.y);
+    float w = fade(x.z);
+    float z0 = lerp(v,
+        lerp(u, perlin3_corner(i, x, ivec3(0, 0, 0), seed), perlin3_corner(i, x, ivec3(1, 0, 0), seed)),
+        lerp(u, perlin3_corner(i, x, ivec3(0, 1, 0), seed), perlin3_corner(i, x, ivec3(1, 1, 0), seed)));
+    float z1 = lerp(v,
+        lerp(u, perlin3_corner(i, x, ivec3(0, 0, 1), seed), perlin3_corner(i, x, ivec3(1, 0, 1), seed)),
+        lerp(u, perlin3_corner(i, x, ivec3(0, 1, 1), seed), perlin3_corner(i, x, ivec3(1, 1, 1), seed)));
+    return PERLIN3_SCALE * lerp(w, z0, z1);
+}
+float perlin4_corner(ivec4 i, vec4 p, ivec4 d, uint seed) {
+    vec4 o = p - vec4(d);
+    return grad4(hash4(i + d, seed), o.x, o.y, o.z, o.w);
+}
+float perlin4_cube(ivec4 i, vec4 x, vec3 f, int dw, uint seed) {
+    float z0 = lerp(f.y,
+        lerp(f.x, perlin4_corner(i, x, ivec4(0, 0, 0, dw), seed), perlin4_corner(i, x, ivec4(1, 0, 0, dw), seed)),
+        lerp(f.x, perlin4_corner(i, x, ivec4(0, 1, 0, dw), seed), perlin4_corner(i, x, ivec4(1, 1, 0, dw), seed)));
+    float z1 = lerp(f.y,
+        lerp(f.x, perlin4_corner(i, x, ivec4(0, 0, 1, dw), seed), perlin4_corner(i, x, ivec4(1, 0, 1, dw), seed)),
+        lerp(f.x, perlin4_corner(i, x, ivec4(0, 1, 1, dw), seed), perlin4_corner(i, x, ivec4(1, 1, 1, dw), seed)));
+    return lerp(f.z, z0, z1);
+}
+float perlin4(vec4 p, uint seed) {
+    vec4 f = floor(p);
+    ivec4 i = ivec4(f);
+    vec4 x = p - f;
+    vec3 fades = vec3(fade(x.x), fade(x.y), fade(x.z));
+    return PERLIN4_SCALE * lerp(fade(x.w), perlin4_cube(i, x, fades, 0, seed), perlin4_cube(i, x, fades, 1, seed));
+}
+
+float falloff(float radius, float length_squared, float gradient) {
+    float t = radius - length_squared;
+    if (t < 0.0) {
+        return 0.0;
+    }
+    t = t * t;
+    return t * t * gradient;
+}
+float simplex2(vec2 p, uint seed) {
+    float s = (p.x + p.y) * SIMPLEX_F2;
+    float i = floor(p.x + s);
+    float j = floor(p.y + s);
+    float t = (i + j) * SIMPLEX_G2;
+    float x0 = p.x - (i - t);
+    float y0 = p.y - (j - t);
+    ivec2 o1 = x0 > y0 ? ivec2(1, 0) : ivec2(0, 1);
+    float x1 = x0 - float(o1.x) + SIMPLEX_G2;
+    float y1 = y0 - float(o1.y) + SIMPLEX_G2;
+    float x2 = x0 - 1.0 + 2.0 * SIMPLEX_G2;
+    float y2 = y0 - 1.0 + 2.0 * SIMPLEX_G2;
+    ivec2 c = ivec2(int(i), int(j));
+    float n0 = falloff(0.5, x0 * x0 + y0 * y0, grad2(hash2(c, seed), x0, y0));
+    float n1 = falloff(0.5, x1 * x1 + y1 * y1, grad2(hash2(c + o1, seed), x1, y1));
+    float n2 = falloff(0.5, x2 * x2 + y2 * y2, grad2(hash2(c + ivec2(1, 1), seed), x2, y2));
+    return SIMPLEX2_SCALE * (n0 + n1 + n2);
+}
+float simplex3_corner(ivec3 c, vec3 p0, ivec3 d, float offset, uint seed) {
+    float x = p0.x - float(d.x) + offset;
+    float y = p0.y - float(d.y) + offset;
+    float z = p0.z - float(d.z) + offset;
+    return falloff(0.6, x * x + y * y + z * z, grad3(hash3(c + d, seed), x, y, z));
+}
+float simplex3(vec3 p, uint seed) {
+    float s = (p.x + p.y + p.z) * SIMPLEX_F3;
+    float i = floor(p.x + s);
+    float j = floor(p.y + s);
+    float k = floor(p.z + s);
+    float t = (i + j + k) * SIMPLEX_G3;
+    vec3 p0 = vec3(p.x - (i - t), p.y - (j - t), p.z - (k - t));
+    ivec3 o1;
+    ivec3 o2;
+    if (p0.x >= p0.y) {
+        if (p0.y >= p0.z) {
+            o1 = ivec3(1, 0, 0); o2 = ivec3(1, 1, 0);
+        } else if (p0.x >= p0.z) {
+            o1 = ivec3(1, 0, 0); o2 = ivec3(1, 0, 1);
+        } else {
+            o1 = ivec3(0, 0, 1); o2 = ivec3(1, 0, 1);
+        }
+    } else if (p0.y < p0.z) {
+        o1 = ivec3(0, 0, 1); o2 = ivec3(0, 1, 1);
+    } else if (p0.x < p0.z) {
+        o1 = ivec3(0, 1, 0); o2 = ivec3(0, 1, 1);
+    } else {
+        o1 = ivec3(0, 1, 0); o2 = ivec3(1, 1, 0);
+    }
+    ivec3 c = ivec3(int(i), int(j), int(k));
+    float n0 = simplex3_corner(c, p0, ivec3(0), 0.0, seed);
+    float n1 = simplex3_corner(c, p0, o1, SIMPLEX_G3, seed);
+    float n2 = simplex3_corner(c, p0, o2, 2.0 * SIMPLEX_G3, seed);
+    float n3 = simplex3_corner(c, p0, ivec3(1), 3.0 * SIMPLEX_G3, seed);
+    return SIMPLEX3_SCALE * (n0 + n1 + n2 + n3);
+}
+float simplex4_corner(ivec4 c, vec4 p0, ivec4 d, float offset, uint seed) {
+    float x = p0.x - float(d.x) + offset;
+    float y = p0.y - float(d.y) + offset;
+    float z = p0.z - float(d.z) + offset;
+    float w = p0.w - float(d.w) + offset;
+    return falloff(0.6, x * x + y * y + z * z + w * w, grad4(hash4(c + d, seed), x, y, z, w));
+}
+float simplex4(vec4 p, uint seed) {
+    float s = (p.x + p.y + p.z + p.w) * SIMPLEX_F4;
+    float i = floor(p.x + s);
+    float j = floor(p.y + s);
+    float k = floor(p.z + s);
+    float l = floor(p.w + s);
+    float t = (i + j + k + l) * SIMPLEX_G4;
+    vec4 p0 = vec4(p.x - (i - t), p.y - (j - t), p.z - (k - t), p.w - (l - t));
+    ivec4 rank = ivec4(0);
+    for (int a = 0; a < 4; a++) {
+        for (int b = a + 1; b < 4; b++) {
+            if (p0[a] > p0[b]) {
+                rank[a] += 1;
+            } else {
+                rank[b] += 1;
+            }
+        }
+    }
+    ivec4 c = ivec4(int(i), int(j), int(k), int(l));
+    float n0 = simplex4_corner(c, p0, ivec4(0), 0.0, seed);
+    float n1 = simplex4_corner(c, p0, ivec4(greaterThanEqual(rank, ivec4(3))), SIMPLEX_G4, seed);
+    float n2 = simplex4_corner(c, p0, ivec4(greaterThanEqual(rank, ivec4(2))), 2.0 * SIMPLEX_G4, seed);
+    float n3 = simplex4_corner(c, p0, ivec4(greaterThanEqual(rank, ivec4(1))), 3.0 * SIMPLEX_G4, seed);
+    float n4 = simplex4_corner(c, p0, ivec4(1), 4.0 * SIMPLEX_G4, seed);
+    return SIMPLEX4_SCALE * (n0 + n1 + n2 + n3 + n4);
+}
+
+float value2(vec2 p, uint seed) {
+    vec2 f = floor(p);
+    ivec2 i = ivec2(f);
+    float u = fade(p.x - f.x);
+    float v = fade(p.y - f.y);
+    return lerp(v,
+        lerp(u, to_signed_unit(hash2(i, seed)), to_signed_unit(hash2(i + ivec2(1, 0), seed))),
+        lerp(u, to_signed_unit(hash2(i + ivec2(0, 1), seed)), to_signed_unit(hash2(i + ivec2(1, 1), seed))));
+}
+float value3(vec3 p, uint seed) {
+    vec3 f = floor(p);
+    ivec3 i = ivec3(f);
+    float u = fade(p.x - f.x);
+    float v = fade(p.y - f.y);
+    float w = fade(p.z - f.z);
+    float z0 = lerp(v,
+        lerp(u, to_signed_unit(hash3(i, seed)), to_signed_unit(hash3(i + ivec3(1, 0, 0), seed))),
+        lerp(u, to_signed_unit(hash3(i + ivec3(0, 1, 0), seed)), to_signed_unit(hash3(i + ivec3(1, 1, 0), seed))));
+    float z1 = lerp(v,
+        lerp(u, to_signed_unit(hash3(i + ivec3(0, 0, 1), seed)), to_signed_unit(hash3(i + ivec3(1, 0, 1), seed))),
+        lerp(u, to_signed_unit(hash3(i + ivec3(0, 1, 1), seed)), to_signed_unit(hash3(i + ivec3(1, 1, 1), seed))));
+    return lerp(w, z0, z1);
+}
+
+//GLSL has no function pointers, so fBm and warping are generated for every noise function
+#define NOISE_FBM(name, noise, vec) \
+float name(vec p, uint seed, uint octaves, float frequency, float lacunarity, float gain) { \
+    float sum = 0.0; \
+    float total = 0.0; \
+    float amplitude = 1.0; \
+    for (uint octave = 0u; octave < octaves; octave++) { \
+        sum += amplitude * noise(p * frequency, seed + octave); \
+        total += amplitude; \
+        amplitude *= gain; \
+        frequency *= lacunarity; \
+    } \
+    return sum / total; \
+}
+NOISE_FBM(fbm_perlin2, perlin2, vec2)
+NOISE_FBM(fbm_perlin3, perlin3, vec3)
+NOISE_FBM(fbm_perlin4, perlin4, vec4)
+NOISE_FBM(fbm_simplex2, simplex2, vec2)
+NOISE_FBM(fbm_simplex3, simplex3, vec3)
+NOISE_FBM(fbm_simplex4, simplex4, vec4)
+NOISE_FBM(fbm_value2, value2, vec2)
+NOISE_FBM(fbm_value3, value3, vec3)
+
+#define NOISE_WARP2(name, noise) \
+float name(vec2 p, float strength, uint seed) { \
+    vec2 q = vec2(noise(p + WARP_OFFSET_1.xy, seed + 1u), noise(p + WARP_OFFSET_2.xy, seed + 2u)); \
+    return noise(p + q * strength, seed); \
+}
+#define NOISE_WARP3(name, noise) \
+float name(vec3 p, float strength, uint seed) { \
+    vec3 q = vec3(noise(p + WARP_OFFSET_1, seed + 1u), noise(p + WARP_OFFSET_2, seed + 2u), \
+        noise(p + WARP_OFFSET_1 + WARP_OFFSET_2, seed + 3u)); \
+    return noise(p + q * strength, seed); \
+}
+NOISE_WARP2(warp_perlin2, perlin2)
+NOISE_WARP2(warp_simplex2, simplex2)
+NOISE_WARP2(warp_value2, value2)
+NOISE_WARP3(warp_perlin3, perlin3)
+NOISE_WARP3(warp_simplex3, simplex3)
+NOISE_WARP3(warp_value3, value3)
@@ -80,11 +80,11 @@ fn main() {
     let data_layout = DataLayout::infer_from_f32slice(&vertices, &[3, 6], gl::FALSE, 4);
     let indices: [u32; 6] = [0, 1, 3, 1, 2, 3];
 
-    let (vao, _vbo, ebo) =
-        VAOBuilder::from_vbo(VBO::default(), &vertices, gl::STATIC_DRAW, data_layout)
-            .add_ebo(EBO::default(), &indices, gl::STATIC_DRAW)
-            .compile();
-    let _ebo = ebo.unwrap();
+    let vbo = VBO::from_data(&vertices, gl::STATIC_DRAW);
+    let ebo = EBO::from_data(&indices, gl::STATIC_DRAW);
+    let vao = VAOBuilder::from_vbo(&vbo, data_layout)
+        .add_ebo(&ebo)
+        .compile();
 
     let mut wireframe = false;
 
@@ -3,17 +3,25 @@ extern crate lib;
 use lib::load_file;
 use lib::setup::quick_setup::initialize_demo;
 use lib::types::buffer::ebo::EBO;
+use lib::types::buffer::ring_buffer::RingBuffer;
 use lib::types::buffer::vao::VAO;
-use lib::types::buffer::vao_builder::VAOBuilder;
-use lib::types::buffer::vbo::VBO;
-use lib::types::data::data_layout::DataLayout;
+use lib::types::data::vertex::Vertex;
 use lib::types::linalg::dimension::Dimension;
-use lib::types::linalg::matrix::Matrix;
+use lib::types::linalg::fixed_matrix::Mat4;
+use lib::types::linalg::vector::{Vec3, Vec4};
 use lib::types::shader::shader::Shader;
 use lib::types::shader::shader_program::ShaderProgram;
 use std::time::SystemTime;
 
 pub const SIERPINSKI_DEPTH: usize = 9;
+
+#[derive(Clone, Copy, Vertex)]
+#[repr(C)]
+pub struct ColoredVertex {
+    position: Vec3<f32>,
+    color: Vec3<f32>,
+}
+
 pub fn main() {
     let demo = initialize_demo("Sierpinski CPU", Dimension::new(900, 700));
     let vertex_shader = Shader::from_source(
@@ -35,18 +43,20 @@ pub fn main() {
         &mut vertices,
         &mut indices,
         SIERPINSKI_DEPTH,
-        Matrix::from_data(vec![-1., -1.0, 0., 1.], Dimension::new(4, 1)),
-        Matrix::from_data(vec![1.0, -1.0, 0., 1.], Dimension::new(4, 1)),
-        Matrix::from_data(vec![0., 1.0, 0., 1.], Dimension::new(4, 1)),
+        Vec4::new(-1., -1.0, 0., 1.),
+        Vec4::new(1.0, -1.0, 0., 1.),
+        Vec4::new(0., 1.0, 0., 1.),
         0.,
     );
-    let data_layout =
-        DataLayout::infer_from_f32slice(&vertices, &[3], gl::FALSE, vertices.len() / 6);
-
-    let (vao, vbo, _ebo): (VAO, VBO, Option<EBO>) =
-        VAOBuilder::from_vbo(VBO::default(), &vertices, gl::DYNAMIC_DRAW, data_layout)
-            .add_ebo(EBO::default(), &indices, gl::DYNAMIC_DRAW)
-            .compile();
+    //The vertices change every frame, the indices stay the same
+    let mut ring = RingBuffer::<ColoredVertex>::new(vertices.len());
+    let vao = VAO::default();
+    ring.attach(&vao, &ColoredVertex::data_layout());
+    let ebo = EBO::from_data(&indices, gl::STATIC_DRAW);
+    vao.bind();
+    ebo.bind();
+    vao.unbind();
+    ebo.unbind();
     let now = SystemTime::now();
     let mut curr_time = SystemTime::now();
     lib::setup::quick_setup::quick_demo(
@@ -62,21 +72,23 @@ pub fn main() {
                     &mut vertices,
                     &mut indices,
                     SIERPINSKI_DEPTH,
-                    Matrix::from_data(vec![-1., -1.0, 0., 1.], Dimension::new(4, 1)),
-                    Matrix::from_data(vec![1.0, -1.0, 0., 1.], Dimension::new(4, 1)),
-                    Matrix::from_data(vec![0., 1.0, 0., 1.], Dimension::new(4, 1)),
+                    Vec4::new(-1., -1.0, 0., 1.),
+                    Vec4::new(1.0, -1.0, 0., 1.),
+                    Vec4::new(0., 1.0, 0., 1.),
                     elapsed_time,
                 );
-                vbo.bind();
-                vbo.buffer_sub_data(&vertices);
+                ring.begin_frame();
+                let base_vertex = ring.write(&vertices).unwrap() as i32;
                 shader_program.gl_use();
                 vao.bind();
-                gl::DrawElements(
+                gl::DrawElementsBaseVertex(
                     gl::TRIANGLES,
                     indices.len() as i32,
                     gl::UNSIGNED_INT,
                     std::ptr::null(),
+                    base_vertex,
                 );
+                ring.end_frame();
             }
             println!(
                 "Current frame took: {}",
@@ -89,35 +101,35 @@ pub fn main() {
 }
 
 pub fn sierpinski_triangle(
-    vertices: &mut Vec<f32>,
+    vertices: &mut Vec<ColoredVertex>,
     indices: &mut Vec<u32>,
     depth: usize,
-    bottom_left: Matrix<f32>,
-    bottom_right: Matrix<f32>,
-    top: Matrix<f32>,
+    bottom_left: Vec4<f32>,
+    bottom_right: Vec4<f32>,
+    top: Vec4<f32>,
     color_signal: f32,
 ) {
     if depth == 0 {
         return;
     }
-    let mut new_bottom = (&bottom_left + &bottom_right) * 0.5;
-    let mut new_left = (&bottom_left + &top) * 0.5;
-    let mut new_right = (&top + &bottom_right) * 0.5;
+    let mut new_bottom = (bottom_left + bottom_right) * 0.5;
+    let mut new_left = (bottom_left + top) * 0.5;
+    let mut new_right = (top + bottom_right) * 0.5;
     sierpinski_triangle(
         vertices,
         indices,
         depth - 1,
-        bottom_left.clone(),
-        new_bottom.clone(),
-        new_left.clone(),
+        bottom_left,
+        new_bottom,
+        new_left,
         color_signal,
     );
     sierpinski_triangle(
         vertices,
         indices,
         depth - 1,
-        new_left.clone(),
-        new_right.clone(),
+        new_left,
+        new_right,
         top,
         color_signal,
     );
@@ -125,35 +137,47 @@ pub fn sierpinski_triangle(
         vertices,
         indices,
         depth - 1,
-        new_bottom.clone(),
-        bottom_right.clone(),
-        new_right.clone(),
+        new_bottom,
+        bottom_right,
+        new_right,
         color_signal,
     );
-    if new_bottom.data[0] != 0. {
-        let mid_point = (&new_bottom + &((&new_left + &new_right) * 0.5)) * 0.5;
-        let matrix = Matrix::<f32>::identity4()
-            .translate4(mid_point.data[0], mid_point.data[1], mid_point.data[2])
-            .rotate4(0., 1., 0., color_signal)
-            .translate4(-mid_point.data[0], -mid_point.data[1], -mid_point.data[2]); //Only need to align x-axes for y-only-rotation
-        new_bottom = &matrix * &new_bottom;
-        new_right = &matrix * &new_right;
-        new_left = &matrix * &new_left;
+    if new_bottom.x != 0. {
+        let mid_point = (new_bottom + (new_left + new_right) * 0.5) * 0.5;
+        let matrix = Mat4::identity()
+            .translate(mid_point.x, mid_point.y, mid_point.z)
+            .rotate(0., 1., 0., color_signal)
+            .translate(-mid_point.x, -mid_point.y, -mid_point.z); //Only need to align x-axes for y-only-rotation
+        new_bottom = matrix * new_bottom;
+        new_right = matrix * new_right;
+        new_left = matrix * new_left;
     }
     let len = indices.len() as u32;
-    vertices.extend_from_slice(&new_right.data[0..3]);
     let mult = 5.;
-    vertices.push((mult * (mult * new_right.data[0] + color_signal)).sin() / 2. + 0.5);
-    vertices.push((mult * (mult * new_right.data[1] + color_signal)).cos() / 2. + 0.5);
-    vertices.push((1.41 * (new_right.data[0] + color_signal)).sin() / 2. + 0.5);
-    vertices.extend_from_slice(&new_bottom.data[0..3]);
-    vertices.push((new_bottom.data[0] + color_signal).sin() / 2. + 0.5);
-    vertices.push((new_bottom.data[1] + color_signal).cos() / 2. + 0.5);
-    vertices.push((1.41 * (new_bottom.data[0] + color_signal)).sin() / 2. + 0.5);
-    vertices.extend_from_slice(&new_left.data[0..3]);
-    vertices.push((new_left.data[0] + color_signal).sin() / 2. + 0.5);
-    vertices.push((new_left.data[1] + color_signal).cos() / 2. + 0.5);
-    vertices.push((1.41 * (new_left.data[0] + color_signal)).sin() / 2. + 0.5);
+    vertices.push(ColoredVertex {
+        position: new_right.truncate(),
+        color: Vec3::new(
+            (mult * (mult * new_right.x + color_signal)).sin() / 2. + 0.5,
+            (mult * (mult * new_right.y + color_signal)).cos() / 2. + 0.5,
+            (1.41 * (new_right.x + color_signal)).sin() / 2. + 0.5,
+        ),
+    });
+    vertices.push(ColoredVertex {
+        position: new_bottom.truncate(),
+        color: Vec3::new(
+            (new_bottom.x + color_signal).sin() / 2. + 0.5,
+            (new_bottom.y + color_signal).cos() / 2. + 0.5,
+            (1.41 * (new_bottom.x + color_signal)).sin() / 2. + 0.5,
+        ),
+    });
+    vertices.push(ColoredVertex {
+        position: new_left.truncate(),
+        color: Vec3::new(
+            (new_left.x + color_signal).sin() / 2. + 0.5,
+            (new_left.y + color_signal).cos() / 2. + 0.5,
+            (1.41 * (new_left.x + color_signal)).sin() / 2. + 0.5,
+        ),
+    });
     indices.push(len);
     indices.push(len + 1);
     indices.push(len + 2);
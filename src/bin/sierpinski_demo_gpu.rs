@@ -41,10 +41,11 @@ pub fn main() {
     );
     let data_layout =
         DataLayout::infer_from_f32slice(&vertices, &[], gl::FALSE, vertices.len() / 2);
-    let (vao, _vbo, _ebo) =
-        VAOBuilder::from_vbo(VBO::default(), &vertices, gl::STATIC_DRAW, data_layout)
-            .add_ebo(EBO::default(), &indices, gl::STATIC_DRAW)
-            .compile();
+    let vbo = VBO::from_data(&vertices, gl::STATIC_DRAW);
+    let ebo = EBO::from_data(&indices, gl::STATIC_DRAW);
+    let vao = VAOBuilder::from_vbo(&vbo, data_layout)
+        .add_ebo(&ebo)
+        .compile();
 
     let now = SystemTime::now();
     let mut curr_time = SystemTime::now();
@@ -1,6 +1,11 @@
 extern crate gl;
 extern crate image;
+#[cfg(feature = "parallel")]
+extern crate rayon;
 extern crate sdl2;
+//Lets #[derive(Vertex)] refer to ::lib from inside this crate too
+extern crate self as lib;
+extern crate vertex_derive;
 
 pub mod setup;
 pub mod types;
@@ -11,17 +11,25 @@ pub struct Demo {
     _gl_context: GLContext, // Make sure that current gl_context isn't dropped
 }
 pub fn initialize_demo(title: &str, dimensions: Dimension) -> Demo {
+    create_context(title, dimensions, false)
+}
+//Context without a visible window, for compute work and tests that need GL
+pub fn initialize_hidden() -> Demo {
+    create_context("hidden", Dimension::new(1, 1), true)
+}
+fn create_context(title: &str, dimensions: Dimension, hidden: bool) -> Demo {
     let sdl = sdl2::init().unwrap();
     let video_subsystem = sdl.video().unwrap();
     let gl_attr = video_subsystem.gl_attr();
     gl_attr.set_context_profile(sdl2::video::GLProfile::Core);
     gl_attr.set_context_version(4, 6);
-    let window = video_subsystem
-        .window(title, dimensions.rows as u32, dimensions.columns as u32)
-        .opengl()
-        .resizable()
-        .build()
-        .unwrap();
+    let mut builder =
+        video_subsystem.window(title, dimensions.rows as u32, dimensions.columns as u32);
+    builder.opengl().resizable();
+    if hidden {
+        builder.hidden();
+    }
+    let window = builder.build().unwrap();
     let gl_context = window.gl_create_context().unwrap();
     window.gl_set_context_to_current().unwrap();
     gl::load_with(|s| video_subsystem.gl_get_proc_address(s) as *const c_void);
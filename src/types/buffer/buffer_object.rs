@@ -0,0 +1,346 @@
+use crate::types::data::packing::Pod;
+use gl::types::*;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
+
+//Binding point a buffer is used with, see VBO, EBO and SSBO
+pub trait BufferTarget {
+    const TARGET: GLenum;
+}
+pub struct ArrayTarget;
+impl BufferTarget for ArrayTarget {
+    const TARGET: GLenum = gl::ARRAY_BUFFER;
+}
+pub struct ElementArrayTarget;
+impl BufferTarget for ElementArrayTarget {
+    const TARGET: GLenum = gl::ELEMENT_ARRAY_BUFFER;
+}
+pub struct ShaderStorageTarget;
+impl BufferTarget for ShaderStorageTarget {
+    const TARGET: GLenum = gl::SHADER_STORAGE_BUFFER;
+}
+
+const USAGES: [GLenum; 9] = [
+    gl::STATIC_DRAW,
+    gl::STATIC_READ,
+    gl::STATIC_COPY,
+    gl::DYNAMIC_DRAW,
+    gl::DYNAMIC_READ,
+    gl::DYNAMIC_COPY,
+    gl::STREAM_DRAW,
+    gl::STREAM_READ,
+    gl::STREAM_COPY,
+];
+
+//GPU array of T. Data transfers use the named (DSA) functions,
+//so they don't disturb the buffers bound to the VAO or any other binding point.
+pub struct Buffer<T: Pod, Target: BufferTarget> {
+    pub id: GLuint,
+    len: usize,
+    capacity: usize,
+    usage: GLenum,
+    phantom: PhantomData<(T, Target)>,
+}
+impl<T: Pod, Target: BufferTarget> Buffer<T, Target> {
+    pub fn from_data(data: &[T], usage: GLenum) -> Self {
+        let mut buffer = Buffer::default();
+        buffer.buffer_data(data, usage);
+        buffer
+    }
+    //Zero initialized, e.g. for the output of a compute shader
+    pub fn zeroed(len: usize, usage: GLenum) -> Self {
+        let mut buffer = Buffer::default();
+        buffer.allocate(len, usage);
+        buffer.len = len;
+        let zero = 0u8;
+        unsafe {
+            gl::ClearNamedBufferData(
+                buffer.id,
+                gl::R8UI,
+                gl::RED_INTEGER,
+                gl::UNSIGNED_BYTE,
+                &zero as *const u8 as *const c_void,
+            )
+        }
+        buffer
+    }
+    pub fn bind(&self) {
+        unsafe { gl::BindBuffer(Target::TARGET, self.id) }
+    }
+    pub fn unbind(&self) {
+        unsafe { gl::BindBuffer(Target::TARGET, 0) }
+    }
+    //For indexed targets, e.g. the storage block bound to binding_point
+    pub fn bind_base(&self, binding_point: GLuint) {
+        unsafe { gl::BindBufferBase(Target::TARGET, binding_point, self.id) }
+    }
+    //Binds the same storage to another target, e.g. gl::ARRAY_BUFFER to draw vertices written by a compute shader
+    pub fn bind_as(&self, target: GLenum) {
+        unsafe { gl::BindBuffer(target, self.id) }
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    //Elements that fit without reallocating
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    fn allocate(&mut self, capacity: usize, usage: GLenum) {
+        debug_assert!(USAGES.contains(&usage));
+        unsafe {
+            gl::NamedBufferData(
+                self.id,
+                (capacity * std::mem::size_of::<T>()) as isize,
+                std::ptr::null(),
+                usage,
+            )
+        }
+        self.capacity = capacity;
+        self.usage = usage;
+    }
+    //Replaces the contents and the storage
+    pub fn buffer_data(&mut self, data: &[T], usage: GLenum) {
+        debug_assert!(USAGES.contains(&usage));
+        unsafe {
+            gl::NamedBufferData(
+                self.id,
+                std::mem::size_of_val(data) as isize,
+                data.as_ptr() as *const c_void,
+                usage,
+            )
+        }
+        self.len = data.len();
+        self.capacity = data.len();
+        self.usage = usage;
+    }
+    //Offset in elements, the data has to fit into the capacity, see reserve
+    pub fn write_at(&mut self, offset: usize, data: &[T]) {
+        debug_assert!(offset <= self.len);
+        assert!(
+            offset + data.len() <= self.capacity,
+            "Writing past the capacity of the buffer, reserve first"
+        );
+        unsafe {
+            gl::NamedBufferSubData(
+                self.id,
+                (offset * std::mem::size_of::<T>()) as isize,
+                std::mem::size_of_val(data) as isize,
+                data.as_ptr() as *const c_void,
+            )
+        }
+        self.len = self.len.max(offset + data.len());
+    }
+    //Appends, growing the storage like reserve if needed
+    pub fn extend(&mut self, data: &[T]) {
+        self.reserve(self.len + data.len());
+        self.write_at(self.len, data)
+    }
+    //Drops the elements past len, the storage is kept
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+    //Grows the storage to at least capacity elements, at least doubling it, and keeps the contents.
+    //The storage is reallocated under the same id, so VAOs and binding points stay valid.
+    pub fn reserve(&mut self, capacity: usize) {
+        if capacity <= self.capacity {
+            return;
+        }
+        let len = self.len;
+        let mut staging = Buffer::<T, Target>::default();
+        if len > 0 {
+            staging.allocate(len, gl::STREAM_COPY);
+            self.copy_to(&mut staging, 0, 0, len);
+        }
+        self.allocate(capacity.max(2 * self.capacity), self.usage);
+        if len > 0 {
+            staging.copy_to(self, 0, 0, len);
+        }
+    }
+    //Copies len elements on the GPU, dst_offset may be at most the length of other
+    pub fn copy_to<U: BufferTarget>(
+        &self,
+        other: &mut Buffer<T, U>,
+        src_offset: usize,
+        dst_offset: usize,
+        len: usize,
+    ) {
+        debug_assert!(src_offset + len <= self.len && dst_offset <= other.len);
+        other.reserve(dst_offset + len);
+        let size = std::mem::size_of::<T>();
+        unsafe {
+            gl::CopyNamedBufferSubData(
+                self.id,
+                other.id,
+                (src_offset * size) as isize,
+                (dst_offset * size) as isize,
+                (len * size) as isize,
+            )
+        }
+        other.len = other.len.max(dst_offset + len);
+    }
+    //Waits for the GPU like read_range, but reads in place
+    pub fn map_read(&mut self, offset: usize, len: usize) -> MappedRange<'_, T> {
+        let pointer = self.map(offset, len, gl::MAP_READ_BIT);
+        MappedRange {
+            id: self.id,
+            data: unsafe { std::slice::from_raw_parts(pointer as *const T, len) },
+        }
+    }
+    pub fn map_read_write(&mut self, offset: usize, len: usize) -> MappedRangeMut<'_, T> {
+        let pointer = self.map(offset, len, gl::MAP_READ_BIT | gl::MAP_WRITE_BIT);
+        MappedRangeMut {
+            id: self.id,
+            data: unsafe { std::slice::from_raw_parts_mut(pointer as *mut T, len) },
+        }
+    }
+    //The old contents of the range are discarded, so the elements start out uninitialized
+    pub fn map_write(&mut self, offset: usize, len: usize) -> MappedRangeMut<'_, MaybeUninit<T>> {
+        let pointer = self.map(
+            offset,
+            len,
+            gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_RANGE_BIT,
+        );
+        MappedRangeMut {
+            id: self.id,
+            data: unsafe { std::slice::from_raw_parts_mut(pointer as *mut MaybeUninit<T>, len) },
+        }
+    }
+    fn map(&mut self, offset: usize, len: usize, access: GLbitfield) -> *mut c_void {
+        debug_assert!(offset + len <= self.len && len > 0);
+        let size = std::mem::size_of::<T>();
+        let pointer = unsafe {
+            gl::MapNamedBufferRange(
+                self.id,
+                (offset * size) as isize,
+                (len * size) as isize,
+                access,
+            )
+        };
+        assert!(!pointer.is_null(), "Mapping the buffer failed");
+        debug_assert!((pointer as usize).is_multiple_of(std::mem::align_of::<T>()));
+        pointer
+    }
+    //Waits for the GPU, after compute shaders call compute_program::buffer_update_barrier first
+    pub fn read(&self) -> Vec<T> {
+        self.read_range(0, self.len)
+    }
+    pub fn read_range(&self, offset: usize, len: usize) -> Vec<T> {
+        debug_assert!(offset + len <= self.len);
+        let size = std::mem::size_of::<T>();
+        //Zeroed so a failed read still yields valid values, and T being Pod accepts whatever GL writes
+        let mut data = vec![MaybeUninit::<T>::zeroed(); len];
+        unsafe {
+            gl::GetNamedBufferSubData(
+                self.id,
+                (offset * size) as isize,
+                (len * size) as isize,
+                data.as_mut_ptr() as *mut c_void,
+            );
+            data.into_iter().map(|value| value.assume_init()).collect()
+        }
+    }
+    //Views the same storage as another element type, e.g. raw bytes
+    pub fn reinterpret<U: Pod>(self) -> Buffer<U, Target> {
+        let bytes = |elements: usize| elements * std::mem::size_of::<T>();
+        let size = std::mem::size_of::<U>();
+        assert!(
+            bytes(self.len) / size * size == bytes(self.len),
+            "The buffer doesn't hold a whole number of elements of the new type"
+        );
+        let buffer = Buffer {
+            id: self.id,
+            len: bytes(self.len) / size,
+            capacity: bytes(self.capacity) / size,
+            usage: self.usage,
+            phantom: PhantomData,
+        };
+        std::mem::forget(self);
+        buffer
+    }
+    pub fn delete(self) {
+        //Deleted by Drop
+    }
+}
+impl<T: Pod, Target: BufferTarget> Default for Buffer<T, Target> {
+    fn default() -> Self {
+        let mut id = 0;
+        unsafe { gl::CreateBuffers(1, &mut id) }
+        Buffer {
+            id,
+            len: 0,
+            capacity: 0,
+            usage: gl::STATIC_DRAW,
+            phantom: PhantomData,
+        }
+    }
+}
+impl<T: Pod, Target: BufferTarget> Drop for Buffer<T, Target> {
+    fn drop(&mut self) {
+        unsafe { gl::DeleteBuffers(1, &self.id) }
+    }
+}
+
+//Range of a buffer mapped for reading, unmapped when dropped.
+//Draw calls may not use the buffer while it is mapped.
+pub struct MappedRange<'a, T> {
+    id: GLuint,
+    data: &'a [T],
+}
+impl<'a, T> MappedRange<'a, T> {
+    //False if the contents got corrupted while mapped, e.g. by a display mode change
+    pub fn unmap(self) -> bool {
+        let id = self.id;
+        std::mem::forget(self);
+        unmap_buffer(id)
+    }
+}
+impl<'a, T> Deref for MappedRange<'a, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.data
+    }
+}
+impl<'a, T> Drop for MappedRange<'a, T> {
+    fn drop(&mut self) {
+        unmap_buffer(self.id);
+    }
+}
+
+//Range of a buffer mapped for writing, E is MaybeUninit<T> if the old contents were discarded
+pub struct MappedRangeMut<'a, E> {
+    id: GLuint,
+    data: &'a mut [E],
+}
+impl<'a, E> MappedRangeMut<'a, E> {
+    //See MappedRange::unmap
+    pub fn unmap(self) -> bool {
+        let id = self.id;
+        std::mem::forget(self);
+        unmap_buffer(id)
+    }
+}
+impl<'a, E> Deref for MappedRangeMut<'a, E> {
+    type Target = [E];
+    fn deref(&self) -> &[E] {
+        self.data
+    }
+}
+impl<'a, E> DerefMut for MappedRangeMut<'a, E> {
+    fn deref_mut(&mut self) -> &mut [E] {
+        self.data
+    }
+}
+impl<'a, E> Drop for MappedRangeMut<'a, E> {
+    fn drop(&mut self) {
+        unmap_buffer(self.id);
+    }
+}
+
+fn unmap_buffer(id: GLuint) -> bool {
+    unsafe { gl::UnmapNamedBuffer(id) == gl::TRUE }
+}
@@ -0,0 +1,100 @@
+use gl::types::*;
+use std::ffi::c_void;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DrawMode {
+    Points,
+    Lines,
+    LineStrip,
+    LineLoop,
+    Triangles,
+    TriangleStrip,
+    TriangleFan,
+    LinesAdjacency,
+    LineStripAdjacency,
+    TrianglesAdjacency,
+    TriangleStripAdjacency,
+    //Vertices per patch, requires a program with tessellation stages
+    Patches(GLint),
+}
+impl DrawMode {
+    pub fn gl_mode(self) -> GLenum {
+        match self {
+            DrawMode::Points => gl::POINTS,
+            DrawMode::Lines => gl::LINES,
+            DrawMode::LineStrip => gl::LINE_STRIP,
+            DrawMode::LineLoop => gl::LINE_LOOP,
+            DrawMode::Triangles => gl::TRIANGLES,
+            DrawMode::TriangleStrip => gl::TRIANGLE_STRIP,
+            DrawMode::TriangleFan => gl::TRIANGLE_FAN,
+            DrawMode::LinesAdjacency => gl::LINES_ADJACENCY,
+            DrawMode::LineStripAdjacency => gl::LINE_STRIP_ADJACENCY,
+            DrawMode::TrianglesAdjacency => gl::TRIANGLES_ADJACENCY,
+            DrawMode::TriangleStripAdjacency => gl::TRIANGLE_STRIP_ADJACENCY,
+            DrawMode::Patches(_) => gl::PATCHES,
+        }
+    }
+    //The input primitive a geometry shader needs for this mode, as in layout(triangles) in
+    pub fn geometry_input(self) -> Option<GLenum> {
+        match self {
+            DrawMode::Points => Some(gl::POINTS),
+            DrawMode::Lines | DrawMode::LineStrip | DrawMode::LineLoop => Some(gl::LINES),
+            DrawMode::Triangles | DrawMode::TriangleStrip | DrawMode::TriangleFan => {
+                Some(gl::TRIANGLES)
+            }
+            DrawMode::LinesAdjacency | DrawMode::LineStripAdjacency => Some(gl::LINES_ADJACENCY),
+            DrawMode::TrianglesAdjacency | DrawMode::TriangleStripAdjacency => {
+                Some(gl::TRIANGLES_ADJACENCY)
+            }
+            //Decided by the tessellation evaluation shader
+            DrawMode::Patches(_) => None,
+        }
+    }
+    //Patch size is global state, so it is set before every draw call with patches
+    fn prepare(self) {
+        if let DrawMode::Patches(vertices) = self {
+            debug_assert!(vertices > 0);
+            unsafe { gl::PatchParameteri(gl::PATCH_VERTICES, vertices) }
+        }
+    }
+    pub fn draw_arrays(self, first: GLint, count: GLsizei) {
+        self.prepare();
+        unsafe { gl::DrawArrays(self.gl_mode(), first, count) }
+    }
+    //Indices of the bound EBO
+    pub fn draw_elements(self, count: GLsizei) {
+        self.prepare();
+        unsafe {
+            gl::DrawElements(
+                self.gl_mode(),
+                count,
+                gl::UNSIGNED_INT,
+                std::ptr::null::<c_void>(),
+            )
+        }
+    }
+    pub fn draw_arrays_instanced(self, first: GLint, count: GLsizei, instances: GLsizei) {
+        self.prepare();
+        unsafe { gl::DrawArraysInstanced(self.gl_mode(), first, count, instances) }
+    }
+    pub fn draw_elements_instanced(self, count: GLsizei, instances: GLsizei) {
+        self.prepare();
+        unsafe {
+            gl::DrawElementsInstanced(
+                self.gl_mode(),
+                count,
+                gl::UNSIGNED_INT,
+                std::ptr::null::<c_void>(),
+                instances,
+            )
+        }
+    }
+}
+
+//Tessellation levels used when a program has an evaluation but no control shader
+pub fn default_tessellation_levels(outer: [f32; 4], inner: [f32; 2]) {
+    unsafe {
+        gl::PatchParameterfv(gl::PATCH_DEFAULT_OUTER_LEVEL, outer.as_ptr());
+        gl::PatchParameterfv(gl::PATCH_DEFAULT_INNER_LEVEL, inner.as_ptr());
+    }
+}
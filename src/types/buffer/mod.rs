@@ -1,4 +1,9 @@
+pub mod buffer_object;
+pub mod draw_mode;
 pub mod ebo;
+pub mod ring_buffer;
+pub mod storage_buffer;
+pub mod uniform_buffer;
 pub mod vao;
 pub mod vao_builder;
 pub mod vbo;
@@ -0,0 +1,179 @@
+use crate::types::buffer::vao::VAO;
+use crate::types::data::data_layout::DataLayout;
+use crate::types::data::packing::Pod;
+use gl::types::*;
+use std::mem::MaybeUninit;
+
+//Regions the CPU writes ahead of the GPU, one for the frame being written and two still in flight
+pub const FRAMES_IN_FLIGHT: usize = 3;
+//How long begin_frame waits on a fence per attempt, in nanoseconds
+const FENCE_TIMEOUT: GLuint64 = 1_000_000;
+
+//Bookkeeping of a ring of equally sized regions, independent of GL
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RingCursor {
+    pub region_len: usize,
+    pub region: usize,
+    //Elements handed out in the current region
+    pub used: usize,
+}
+impl RingCursor {
+    pub fn new(region_len: usize) -> Self {
+        RingCursor {
+            region_len,
+            region: 0,
+            used: 0,
+        }
+    }
+    //Index of the first element in the whole buffer, None if the region is full
+    pub fn allocate(&mut self, len: usize) -> Option<usize> {
+        if self.used + len > self.region_len {
+            return None;
+        }
+        let first = self.region * self.region_len + self.used;
+        self.used += len;
+        Some(first)
+    }
+    pub fn advance(&mut self) {
+        self.region = (self.region + 1) % FRAMES_IN_FLIGHT;
+        self.used = 0;
+    }
+}
+
+pub struct RingAllocation<'a, T> {
+    //Index of data[0] in the buffer, the first vertex or base vertex of the draw call
+    pub first: usize,
+    //Write-only mapped memory, its contents are undefined until written
+    pub data: &'a mut [MaybeUninit<T>],
+}
+impl<'a, T: Copy> RingAllocation<'a, T> {
+    //Fills the whole allocation, values has to have the same length
+    pub fn copy_from_slice(&mut self, values: &[T]) {
+        assert_eq!(self.data.len(), values.len());
+        for (element, value) in self.data.iter_mut().zip(values) {
+            element.write(*value);
+        }
+    }
+}
+
+//Streaming vertex buffer, persistently mapped so writing never waits for glBufferSubData.
+//Every frame: begin_frame, allocate and fill or write, draw, end_frame.
+pub struct RingBuffer<T: Pod> {
+    pub id: GLuint,
+    pointer: *mut MaybeUninit<T>,
+    cursor: RingCursor,
+    fences: [GLsync; FRAMES_IN_FLIGHT],
+}
+impl<T: Pod> RingBuffer<T> {
+    //Room for region_len elements per frame
+    pub fn new(region_len: usize) -> Self {
+        debug_assert!(region_len > 0);
+        let size = (FRAMES_IN_FLIGHT * region_len * std::mem::size_of::<T>()) as isize;
+        let flags = gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT;
+        let mut id = 0;
+        let pointer = unsafe {
+            gl::CreateBuffers(1, &mut id);
+            gl::NamedBufferStorage(id, size, std::ptr::null(), flags);
+            gl::MapNamedBufferRange(id, 0, size, flags) as *mut MaybeUninit<T>
+        };
+        assert!(!pointer.is_null(), "Mapping the ring buffer failed");
+        RingBuffer {
+            id,
+            pointer,
+            cursor: RingCursor::new(region_len),
+            fences: [std::ptr::null(); FRAMES_IN_FLIGHT],
+        }
+    }
+    pub fn bind(&self) {
+        unsafe { gl::BindBuffer(gl::ARRAY_BUFFER, self.id) }
+    }
+    pub fn unbind(&self) {
+        unsafe { gl::BindBuffer(gl::ARRAY_BUFFER, 0) }
+    }
+    //Points the attributes of the layout at this buffer, allocations are then drawn with their first index
+    pub fn attach(&self, vao: &VAO, layout: &DataLayout) {
+        vao.bind();
+        self.bind();
+        layout.vertex_attrib_pointer();
+        vao.unbind();
+        self.unbind();
+    }
+    pub fn region_len(&self) -> usize {
+        self.cursor.region_len
+    }
+    //Moves on to the next region, only waits if the GPU is still reading it from three frames ago
+    pub fn begin_frame(&mut self) {
+        self.cursor.advance();
+        let fence = std::mem::replace(&mut self.fences[self.cursor.region], std::ptr::null());
+        if fence.is_null() {
+            return;
+        }
+        unsafe {
+            loop {
+                let status = gl::ClientWaitSync(fence, gl::SYNC_FLUSH_COMMANDS_BIT, FENCE_TIMEOUT);
+                if status == gl::ALREADY_SIGNALED
+                    || status == gl::CONDITION_SATISFIED
+                    || status == gl::WAIT_FAILED
+                {
+                    break;
+                }
+            }
+            gl::DeleteSync(fence);
+        }
+    }
+    //Space for len elements in this frame's region, None if the region is full
+    pub fn allocate(&mut self, len: usize) -> Option<RingAllocation<'_, T>> {
+        let first = self.cursor.allocate(len)?;
+        let data = unsafe { std::slice::from_raw_parts_mut(self.pointer.add(first), len) };
+        Some(RingAllocation { first, data })
+    }
+    //Allocates and fills in one go, returns the index of data[0] like RingAllocation::first
+    pub fn write(&mut self, data: &[T]) -> Option<usize> {
+        let mut allocation = self.allocate(data.len())?;
+        allocation.copy_from_slice(data);
+        Some(allocation.first)
+    }
+    //Call after the draw calls that read this frame's region
+    pub fn end_frame(&mut self) {
+        let region = self.cursor.region;
+        unsafe {
+            if !self.fences[region].is_null() {
+                gl::DeleteSync(self.fences[region]);
+            }
+            self.fences[region] = gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0);
+        }
+    }
+}
+impl<T: Pod> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        unsafe {
+            for fence in self.fences.iter().filter(|f| !f.is_null()) {
+                gl::DeleteSync(*fence);
+            }
+            gl::UnmapNamedBuffer(self.id);
+            gl::DeleteBuffers(1, &self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_ring_cursor() {
+        let mut cursor = RingCursor::new(100);
+        assert_eq!(cursor.allocate(60), Some(0));
+        assert_eq!(cursor.allocate(40), Some(60));
+        assert_eq!(cursor.allocate(1), None);
+        cursor.advance();
+        assert_eq!(cursor.allocate(10), Some(100));
+        cursor.advance();
+        assert_eq!(cursor.allocate(101), None);
+        assert_eq!(cursor.allocate(100), Some(200));
+        //Wraps around to the region of three frames ago
+        cursor.advance();
+        assert_eq!(cursor, RingCursor::new(100));
+        assert_eq!(cursor.allocate(5), Some(0));
+    }
+}
@@ -0,0 +1,6 @@
+use crate::types::buffer::buffer_object::{Buffer, ShaderStorageTarget};
+
+//T has to match the std430 layout of the GLSL struct,
+//e.g. a #[repr(C)] struct padded to the alignment of its largest member
+pub type SSBO<T> = ShaderStorageBuffer<T>;
+pub type ShaderStorageBuffer<T> = Buffer<T, ShaderStorageTarget>;
@@ -0,0 +1,146 @@
+use crate::types::data::block_layout::{BlockBuffer, BlockError, BlockLayout, BlockValue};
+use gl::types::*;
+use std::ffi::c_void;
+use std::ops::Range;
+
+pub type UBO = UniformBuffer;
+
+//Uniform block data shared between programs, see ShaderProgram::bind_uniform_block
+pub struct UniformBuffer {
+    pub id: GLuint,
+    block: BlockBuffer,
+    //Bytes written since the last flush
+    dirty: Option<Range<usize>>,
+}
+impl UBO {
+    //The block has to use BlockLayout::Std140, the layout of uniform blocks declared with layout(std140)
+    pub fn new(block: BlockBuffer, draw_type: GLenum) -> Self {
+        assert!(
+            block.layout() == BlockLayout::Std140,
+            "Uniform buffers need a std140 block"
+        );
+        debug_assert!([gl::STATIC_DRAW, gl::DYNAMIC_DRAW, gl::STREAM_DRAW].contains(&draw_type));
+        let mut id = 0;
+        unsafe {
+            gl::GenBuffers(1, &mut id);
+            gl::BindBuffer(gl::UNIFORM_BUFFER, id);
+            gl::BufferData(
+                gl::UNIFORM_BUFFER,
+                block.as_bytes().len() as isize,
+                block.as_bytes().as_ptr() as *const c_void,
+                draw_type,
+            );
+            gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+        }
+        UniformBuffer {
+            id,
+            block,
+            dirty: None,
+        }
+    }
+    pub fn bind(&self) {
+        unsafe { gl::BindBuffer(gl::UNIFORM_BUFFER, self.id) }
+    }
+    pub fn unbind(&self) {
+        unsafe { gl::BindBuffer(gl::UNIFORM_BUFFER, 0) }
+    }
+    //Makes the buffer visible to every block bound to binding_point
+    pub fn bind_base(&self, binding_point: GLuint) {
+        unsafe { gl::BindBufferBase(gl::UNIFORM_BUFFER, binding_point, self.id) }
+    }
+    //Offset has to be a multiple of GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT
+    pub fn bind_range(&self, binding_point: GLuint, offset: usize, size: usize) {
+        debug_assert!(offset + size <= self.block.as_bytes().len());
+        unsafe {
+            gl::BindBufferRange(
+                gl::UNIFORM_BUFFER,
+                binding_point,
+                self.id,
+                offset as isize,
+                size as isize,
+            )
+        }
+    }
+    pub fn block(&self) -> &BlockBuffer {
+        &self.block
+    }
+    //Uploads only the bytes of the member, e.g. set("view", &view)
+    pub fn set<T: BlockValue>(&mut self, path: &str, value: &T) -> Result<(), BlockError> {
+        let range = self.block.set(path, value)?;
+        self.buffer_sub_data(range);
+        Ok(())
+    }
+    //Like set, but the upload is deferred to flush so several members go up in one call
+    pub fn write<T: BlockValue>(&mut self, path: &str, value: &T) -> Result<(), BlockError> {
+        let range = self.block.set(path, value)?;
+        self.dirty = Some(merge_ranges(self.dirty.take(), range));
+        Ok(())
+    }
+    pub fn flush(&mut self) {
+        if let Some(range) = self.dirty.take() {
+            self.buffer_sub_data(range);
+        }
+    }
+    fn buffer_sub_data(&self, range: Range<usize>) {
+        let bytes = &self.block.as_bytes()[range.clone()];
+        unsafe {
+            gl::BindBuffer(gl::UNIFORM_BUFFER, self.id);
+            gl::BufferSubData(
+                gl::UNIFORM_BUFFER,
+                range.start as isize,
+                bytes.len() as isize,
+                bytes.as_ptr() as *const c_void,
+            );
+            gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+        }
+    }
+    pub fn delete(self) {
+        unsafe { gl::DeleteBuffers(1, &self.id) }
+    }
+}
+impl Drop for UBO {
+    fn drop(&mut self) {
+        unsafe { gl::DeleteBuffers(1, &self.id) }
+    }
+}
+
+//Smallest range covering both, one upload of the gap is cheaper than several calls
+fn merge_ranges(dirty: Option<Range<usize>>, range: Range<usize>) -> Range<usize> {
+    match dirty {
+        Some(dirty) => dirty.start.min(range.start)..dirty.end.max(range.end),
+        None => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::data::block_layout::{BlockMember, BlockType, ScalarType};
+    use crate::types::linalg::vector::Vec4;
+
+    #[test]
+    pub fn test_merge_ranges() {
+        assert_eq!(merge_ranges(None, 4..8), 4..8);
+        assert_eq!(merge_ranges(Some(16..32), 4..8), 4..32);
+        assert_eq!(merge_ranges(Some(4..8), 40..48), 4..48);
+        assert_eq!(merge_ranges(Some(0..64), 16..32), 0..64);
+
+        //What write collects for a flush of two members
+        let mut block = BlockBuffer::new(
+            BlockLayout::Std140,
+            vec![
+                BlockMember::new("time", BlockType::Scalar(ScalarType::Float)),
+                BlockMember::new("color", BlockType::Vector(ScalarType::Float, 4)),
+                BlockMember::new("scale", BlockType::Scalar(ScalarType::Float)),
+            ],
+        );
+        let scale = block.set("scale", &2f32).unwrap();
+        let color = block.set("color", &Vec4::new(1f32, 0., 0., 1.)).unwrap();
+        let dirty = merge_ranges(Some(scale), color);
+        assert_eq!(dirty, 16..36);
+        assert_eq!(
+            merge_ranges(Some(dirty), block.set("time", &1f32).unwrap()),
+            0..36
+        );
+    }
+}
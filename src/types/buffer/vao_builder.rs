@@ -2,60 +2,77 @@ use crate::types::buffer::ebo::EBO;
 use crate::types::buffer::vao::VAO;
 use crate::types::buffer::vbo::VBO;
 use crate::types::data::data_layout::DataLayout;
+use crate::types::data::packing::Pod;
+use crate::types::data::vertex::Vertex;
 use gl::types::*;
 
 pub type VAOBuilder<'a> = VertexArrayObjectBuilder<'a>;
 
-pub struct VertexArrayObjectBuilder<'a> {
-    pub vbo: VBO,
-    pub vbo_data: &'a [f32],
-    pub vbo_draw_type: GLenum,
+//One VBO of a VAO together with the attributes it feeds
+pub struct VertexBufferBinding {
+    pub vbo: GLuint,
     pub data_layout: DataLayout,
-    pub ebo: Option<EBO>,
-    pub ebo_data: Option<&'a [u32]>,
-    pub ebo_draw_type: Option<GLenum>,
+}
+
+//Records which buffers feed the VAO, the caller keeps the typed buffers and fills them,
+//e.g. with VBO::from_data. Their ids stay the same when they grow, so the VAO stays valid.
+pub struct VertexArrayObjectBuilder<'a> {
+    pub vbos: Vec<VertexBufferBinding>,
+    pub ebo: Option<&'a EBO>,
 }
 impl<'a> VAOBuilder<'a> {
-    pub fn from_vbo(
-        vbo: VBO,
-        vbo_data: &'a [f32],
-        vbo_draw_type: GLenum,
-        data_layout: DataLayout,
-    ) -> Self {
+    pub fn from_vbo<T: Pod>(vbo: &'a VBO<T>, data_layout: DataLayout) -> Self {
         VAOBuilder {
-            vbo,
-            vbo_data,
-            vbo_draw_type,
-            data_layout,
+            vbos: vec![],
             ebo: None,
-            ebo_data: None,
-            ebo_draw_type: None,
         }
+        .add_vbo(vbo, data_layout)
+    }
+    //Layout taken from the vertex type, see #[derive(Vertex)]
+    pub fn from_vertices<V: Vertex>(vbo: &'a VBO<V>) -> Self {
+        VAOBuilder::from_vbo(vbo, V::data_layout())
     }
-    pub fn add_ebo(mut self, ebo: EBO, ebo_data: &'a [u32], ebo_draw_type: GLenum) -> Self {
+    //Further streams, e.g. normals in their own buffer or per-instance data with a divisor
+    pub fn add_vbo<T: Pod>(mut self, vbo: &'a VBO<T>, data_layout: DataLayout) -> Self {
+        assert!(
+            self.vbos.iter().all(|binding| binding
+                .data_layout
+                .locations()
+                .iter()
+                .all(|l| !data_layout.locations().contains(l))),
+            "The layout uses locations that another VBO of the VAO already feeds"
+        );
+        self.vbos.push(VertexBufferBinding {
+            vbo: vbo.id,
+            data_layout,
+        });
+        self
+    }
+    pub fn add_vertices<V: Vertex>(self, vbo: &'a VBO<V>) -> Self {
+        self.add_vbo(vbo, V::data_layout())
+    }
+    pub fn add_ebo(mut self, ebo: &'a EBO) -> Self {
         self.ebo = Some(ebo);
-        self.ebo_data = Some(ebo_data);
-        self.ebo_draw_type = Some(ebo_draw_type);
         self
     }
-    pub fn compile(self) -> (VAO, VBO, Option<EBO>) {
+    pub fn compile(self) -> VAO {
         let vao = VAO::default();
         vao.bind();
 
-        self.vbo.bind();
-        self.vbo.buffer_data(self.vbo_data, self.vbo_draw_type);
+        //The attribute pointers capture the VBO bound to GL_ARRAY_BUFFER at the time of the call
+        for binding in self.vbos.iter() {
+            unsafe { gl::BindBuffer(gl::ARRAY_BUFFER, binding.vbo) }
+            binding.data_layout.vertex_attrib_pointer();
+        }
 
-        if self.ebo.is_some() {
-            let ebo = self.ebo.as_ref().unwrap();
+        if let Some(ebo) = self.ebo {
             ebo.bind();
-            ebo.buffer_data(self.ebo_data.unwrap(), self.ebo_draw_type.unwrap());
         }
-        self.data_layout.vertex_attrib_pointer();
         vao.unbind();
-        self.vbo.unbind();
-        if self.ebo.is_some() {
-            self.ebo.as_ref().unwrap().unbind();
+        unsafe { gl::BindBuffer(gl::ARRAY_BUFFER, 0) }
+        if let Some(ebo) = self.ebo {
+            ebo.unbind();
         }
-        (vao, self.vbo, self.ebo)
+        vao
     }
 }
@@ -0,0 +1,463 @@
+use crate::types::linalg::fixed_matrix::{Mat3, Mat4};
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+use std::fmt;
+
+//GLSL memory layouts of interface blocks, std140 for uniform blocks and std430 for storage blocks
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlockLayout {
+    Std140,
+    Std430,
+}
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScalarType {
+    Float,
+    Int,
+    UnsignedInt,
+    //Stored as a 32 bit integer
+    Bool,
+    Double,
+}
+impl ScalarType {
+    pub fn size(self) -> u32 {
+        if self == ScalarType::Double {
+            8
+        } else {
+            4
+        }
+    }
+}
+#[derive(Clone, PartialEq, Debug)]
+pub enum BlockType {
+    Scalar(ScalarType),
+    Vector(ScalarType, u32),
+    //Column-major, every column is stored like an array element of type vec<rows>
+    Matrix {
+        scalar: ScalarType,
+        columns: u32,
+        rows: u32,
+    },
+    Array(Box<BlockType>, u32),
+    Struct(Vec<BlockMember>),
+}
+#[derive(Clone, PartialEq, Debug)]
+pub struct BlockMember {
+    pub name: String,
+    pub block_type: BlockType,
+}
+impl BlockMember {
+    pub fn new(name: &str, block_type: BlockType) -> Self {
+        BlockMember {
+            name: name.to_owned(),
+            block_type,
+        }
+    }
+}
+
+fn round_up(value: u32, alignment: u32) -> u32 {
+    value.div_ceil(alignment) * alignment
+}
+
+impl BlockLayout {
+    pub fn alignment(self, block_type: &BlockType) -> u32 {
+        match block_type {
+            BlockType::Scalar(scalar) => scalar.size(),
+            //vec3 is aligned like vec4
+            BlockType::Vector(scalar, n) => scalar.size() * if *n == 2 { 2 } else { 4 },
+            BlockType::Matrix { scalar, rows, .. } => {
+                self.array_alignment(&BlockType::Vector(*scalar, *rows))
+            }
+            BlockType::Array(element, _) => self.array_alignment(element),
+            BlockType::Struct(members) => {
+                let alignment = members
+                    .iter()
+                    .map(|m| self.alignment(&m.block_type))
+                    .max()
+                    .unwrap_or(1);
+                self.round_to_vec4(alignment)
+            }
+        }
+    }
+    //Including the padding at the end of arrays and structs
+    pub fn size(self, block_type: &BlockType) -> u32 {
+        match block_type {
+            BlockType::Scalar(scalar) => scalar.size(),
+            BlockType::Vector(scalar, n) => scalar.size() * n,
+            BlockType::Matrix {
+                scalar,
+                columns,
+                rows,
+            } => self.array_stride(&BlockType::Vector(*scalar, *rows)) * columns,
+            BlockType::Array(element, length) => self.array_stride(element) * length,
+            BlockType::Struct(members) => {
+                let end = match (members.last(), self.offsets(members).last()) {
+                    (Some(last), Some(offset)) => offset + self.size(&last.block_type),
+                    _ => 0,
+                };
+                round_up(end, self.alignment(block_type))
+            }
+        }
+    }
+    //Distance between two array elements, also the column stride of matrices
+    pub fn array_stride(self, element: &BlockType) -> u32 {
+        round_up(self.size(element), self.array_alignment(element))
+    }
+    pub fn offsets(self, members: &[BlockMember]) -> Vec<u32> {
+        let mut offset = 0;
+        members
+            .iter()
+            .map(|member| {
+                let member_offset = round_up(offset, self.alignment(&member.block_type));
+                offset = member_offset + self.size(&member.block_type);
+                member_offset
+            })
+            .collect()
+    }
+    //Offset and type of a member path like "lights[2].color" inside a struct
+    pub fn find<'a>(
+        self,
+        block_type: &'a BlockType,
+        path: &str,
+    ) -> Result<(u32, &'a BlockType), BlockError> {
+        let mut offset = 0;
+        let mut current = block_type;
+        for segment in path.split('.') {
+            let (name, indices) = match segment.find('[') {
+                Some(i) => (&segment[..i], &segment[i..]),
+                None => (segment, ""),
+            };
+            let members = match current {
+                BlockType::Struct(members) => members,
+                _ => return Err(BlockError::UnknownMember(path.to_owned())),
+            };
+            let index = members
+                .iter()
+                .position(|m| m.name == name)
+                .ok_or_else(|| BlockError::UnknownMember(path.to_owned()))?;
+            offset += self.offsets(members)[index];
+            current = &members[index].block_type;
+            for index in indices.split(']').filter(|s| !s.is_empty()) {
+                let index: u32 = index
+                    .strip_prefix('[')
+                    .and_then(|index| index.parse().ok())
+                    .ok_or_else(|| BlockError::UnknownMember(path.to_owned()))?;
+                let (stride, element, length) = match current {
+                    BlockType::Array(element, length) => {
+                        (self.array_stride(element), &**element, *length)
+                    }
+                    _ => return Err(BlockError::UnknownMember(path.to_owned())),
+                };
+                if index >= length {
+                    return Err(BlockError::IndexOutOfBounds {
+                        path: path.to_owned(),
+                        length,
+                    });
+                }
+                offset += index * stride;
+                current = element;
+            }
+        }
+        Ok((offset, current))
+    }
+    fn array_alignment(self, element: &BlockType) -> u32 {
+        self.round_to_vec4(self.alignment(element))
+    }
+    //std140 rounds the alignment of arrays and structs up to that of a vec4
+    fn round_to_vec4(self, alignment: u32) -> u32 {
+        match self {
+            BlockLayout::Std140 => round_up(alignment, 16),
+            BlockLayout::Std430 => alignment,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum BlockError {
+    UnknownMember(String),
+    IndexOutOfBounds {
+        path: String,
+        length: u32,
+    },
+    TypeMismatch {
+        path: String,
+        expected: BlockType,
+        actual: BlockType,
+    },
+}
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockError::UnknownMember(path) => write!(f, "Block has no member {}", path),
+            BlockError::IndexOutOfBounds { path, length } => {
+                write!(
+                    f,
+                    "Index out of bounds in {}, the array has {} elements",
+                    path, length
+                )
+            }
+            BlockError::TypeMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Member {} has type {:?} but got {:?}",
+                path, expected, actual
+            ),
+        }
+    }
+}
+
+//Rust values with a GLSL counterpart, written at the start of bytes following the layout rules
+pub trait BlockValue {
+    fn block_type() -> BlockType;
+    fn write(&self, layout: BlockLayout, bytes: &mut [u8]);
+}
+macro_rules! impl_scalar_block_value {
+    ($t: ty, $scalar: expr) => {
+        impl BlockValue for $t {
+            fn block_type() -> BlockType {
+                BlockType::Scalar($scalar)
+            }
+            fn write(&self, _: BlockLayout, bytes: &mut [u8]) {
+                bytes[..std::mem::size_of::<$t>()].copy_from_slice(&self.to_ne_bytes());
+            }
+        }
+        impl BlockValue for Vec2<$t> {
+            fn block_type() -> BlockType {
+                BlockType::Vector($scalar, 2)
+            }
+            fn write(&self, layout: BlockLayout, bytes: &mut [u8]) {
+                write_components(&self.to_array(), layout, bytes);
+            }
+        }
+        impl BlockValue for Vec3<$t> {
+            fn block_type() -> BlockType {
+                BlockType::Vector($scalar, 3)
+            }
+            fn write(&self, layout: BlockLayout, bytes: &mut [u8]) {
+                write_components(&self.to_array(), layout, bytes);
+            }
+        }
+        impl BlockValue for Vec4<$t> {
+            fn block_type() -> BlockType {
+                BlockType::Vector($scalar, 4)
+            }
+            fn write(&self, layout: BlockLayout, bytes: &mut [u8]) {
+                write_components(&self.to_array(), layout, bytes);
+            }
+        }
+    };
+}
+impl_scalar_block_value!(f32, ScalarType::Float);
+impl_scalar_block_value!(i32, ScalarType::Int);
+impl_scalar_block_value!(u32, ScalarType::UnsignedInt);
+impl_scalar_block_value!(f64, ScalarType::Double);
+impl BlockValue for bool {
+    fn block_type() -> BlockType {
+        BlockType::Scalar(ScalarType::Bool)
+    }
+    fn write(&self, layout: BlockLayout, bytes: &mut [u8]) {
+        (*self as u32).write(layout, bytes);
+    }
+}
+//Vector components are tightly packed, unlike array elements
+fn write_components<T: BlockValue>(components: &[T], layout: BlockLayout, bytes: &mut [u8]) {
+    let size = layout.size(&T::block_type()) as usize;
+    for (i, component) in components.iter().enumerate() {
+        component.write(layout, &mut bytes[i * size..]);
+    }
+}
+impl<T: BlockValue, const N: usize> BlockValue for [T; N] {
+    fn block_type() -> BlockType {
+        BlockType::Array(Box::new(T::block_type()), N as u32)
+    }
+    fn write(&self, layout: BlockLayout, bytes: &mut [u8]) {
+        let stride = layout.array_stride(&T::block_type()) as usize;
+        for (i, value) in self.iter().enumerate() {
+            value.write(layout, &mut bytes[i * stride..]);
+        }
+    }
+}
+macro_rules! impl_matrix_block_value {
+    ($mat: ident, $n: expr) => {
+        impl BlockValue for $mat<f32> {
+            fn block_type() -> BlockType {
+                BlockType::Matrix {
+                    scalar: ScalarType::Float,
+                    columns: $n,
+                    rows: $n,
+                }
+            }
+            fn write(&self, layout: BlockLayout, bytes: &mut [u8]) {
+                let stride =
+                    layout.array_stride(&BlockType::Vector(ScalarType::Float, $n)) as usize;
+                //Our matrices are row-major
+                for column in 0..$n {
+                    for row in 0..$n {
+                        self.get(row, column)
+                            .write(layout, &mut bytes[column * stride + row * 4..]);
+                    }
+                }
+            }
+        }
+    };
+}
+impl_matrix_block_value!(Mat3, 3);
+impl_matrix_block_value!(Mat4, 4);
+
+//CPU side copy of an interface block, e.g. the contents of a uniform buffer
+pub struct BlockBuffer {
+    layout: BlockLayout,
+    block_type: BlockType,
+    bytes: Vec<u8>,
+}
+impl BlockBuffer {
+    pub fn new(layout: BlockLayout, members: Vec<BlockMember>) -> Self {
+        let block_type = BlockType::Struct(members);
+        let bytes = vec![0; layout.size(&block_type) as usize];
+        BlockBuffer {
+            layout,
+            block_type,
+            bytes,
+        }
+    }
+    pub fn layout(&self) -> BlockLayout {
+        self.layout
+    }
+    pub fn block_type(&self) -> &BlockType {
+        &self.block_type
+    }
+    pub fn offset(&self, path: &str) -> Result<u32, BlockError> {
+        self.layout.find(&self.block_type, path).map(|(o, _)| o)
+    }
+    //Returns the byte range that changed, e.g. for a sub-range upload
+    pub fn set<T: BlockValue>(
+        &mut self,
+        path: &str,
+        value: &T,
+    ) -> Result<std::ops::Range<usize>, BlockError> {
+        let (offset, expected) = self.layout.find(&self.block_type, path)?;
+        let actual = T::block_type();
+        if *expected != actual {
+            return Err(BlockError::TypeMismatch {
+                path: path.to_owned(),
+                expected: expected.clone(),
+                actual,
+            });
+        }
+        let range = offset as usize..(offset + self.layout.size(&actual)) as usize;
+        value.write(self.layout, &mut self.bytes[range.clone()]);
+        Ok(range)
+    }
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn float() -> BlockType {
+        BlockType::Scalar(ScalarType::Float)
+    }
+    fn vec(n: u32) -> BlockType {
+        BlockType::Vector(ScalarType::Float, n)
+    }
+    fn light() -> BlockType {
+        BlockType::Struct(vec![
+            BlockMember::new("position", vec(3)),
+            BlockMember::new("intensity", float()),
+            BlockMember::new("color", vec(3)),
+        ])
+    }
+    fn members() -> Vec<BlockMember> {
+        vec![
+            BlockMember::new("time", float()),
+            BlockMember::new("offset", vec(2)),
+            BlockMember::new("weights", BlockType::Array(Box::new(float()), 3)),
+            BlockMember::new("normal", Mat3::<f32>::block_type()),
+            BlockMember::new("lights", BlockType::Array(Box::new(light()), 2)),
+            BlockMember::new("count", BlockType::Scalar(ScalarType::Int)),
+        ]
+    }
+
+    #[test]
+    pub fn test_std140_offsets() {
+        let layout = BlockLayout::Std140;
+        assert_eq!(layout.offsets(&members()), vec![0, 8, 16, 64, 112, 176]);
+        //vec3 followed by a float shares one vec4 slot
+        assert_eq!(layout.size(&light()), 32);
+        assert_eq!(layout.alignment(&light()), 16);
+        assert_eq!(layout.array_stride(&float()), 16);
+        assert_eq!(layout.size(&BlockType::Struct(members())), 192);
+        let block = BlockType::Struct(members());
+        let (offset, block_type) = layout.find(&block, "lights[1].color").unwrap();
+        assert_eq!((offset, block_type), (112 + 32 + 16, &vec(3)));
+    }
+
+    #[test]
+    pub fn test_std430_offsets() {
+        let layout = BlockLayout::Std430;
+        assert_eq!(layout.offsets(&members()), vec![0, 8, 16, 32, 80, 144]);
+        assert_eq!(layout.array_stride(&float()), 4);
+        assert_eq!(layout.array_stride(&vec(3)), 16);
+        assert_eq!(layout.array_stride(&vec(2)), 8);
+        assert_eq!(layout.size(&BlockType::Struct(members())), 160);
+        let doubles = BlockType::Struct(vec![
+            BlockMember::new("a", float()),
+            BlockMember::new("b", BlockType::Vector(ScalarType::Double, 3)),
+        ]);
+        assert_eq!(layout.size(&doubles), 64);
+        let small = BlockType::Struct(vec![BlockMember::new("a", float())]);
+        assert_eq!(layout.alignment(&small), 4);
+        assert_eq!(BlockLayout::Std140.alignment(&small), 16);
+    }
+
+    #[test]
+    pub fn test_block_buffer() {
+        let mut block = BlockBuffer::new(BlockLayout::Std140, members());
+        assert_eq!(block.as_bytes().len(), 192);
+        assert_eq!(block.set("time", &2f32), Ok(0..4));
+        block.set("weights", &[1f32, 2., 3.]).unwrap();
+        let normal = Mat3::from_data([1., 2., 3., 4., 5., 6., 7., 8., 9.]);
+        block.set("normal", &normal).unwrap();
+        block
+            .set("lights[1].color", &Vec3::new(0.5f32, 0.25, 1.))
+            .unwrap();
+        block.set("count", &7i32).unwrap();
+        let read = |offset: usize| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(&block.as_bytes()[offset..offset + 4]);
+            f32::from_ne_bytes(bytes)
+        };
+        assert_eq!(read(0), 2.);
+        assert_eq!((read(16), read(32), read(48)), (1., 2., 3.));
+        //Columns of the row-major matrix, 16 bytes apart
+        assert_eq!((read(64), read(68), read(72)), (1., 4., 7.));
+        assert_eq!((read(80), read(84), read(96)), (2., 5., 3.));
+        assert_eq!((read(160), read(164), read(168)), (0.5, 0.25, 1.));
+        assert_eq!(&block.as_bytes()[176..180], &7i32.to_ne_bytes());
+
+        assert_eq!(
+            block.set("time", &1i32),
+            Err(BlockError::TypeMismatch {
+                path: "time".to_owned(),
+                expected: float(),
+                actual: BlockType::Scalar(ScalarType::Int),
+            })
+        );
+        assert_eq!(
+            block.set("lights[2].color", &Vec3::new(0f32, 0., 0.)),
+            Err(BlockError::IndexOutOfBounds {
+                path: "lights[2].color".to_owned(),
+                length: 2
+            })
+        );
+        assert!(block.set("light", &0f32).is_err());
+        assert_eq!(
+            block.set("weights[1]é", &0f32),
+            Err(BlockError::UnknownMember("weights[1]é".to_owned()))
+        );
+    }
+}
@@ -1,16 +1,55 @@
-use crate::types::data::data_specification::DataSpecification;
+use crate::types::data::data_specification::{ComponentType, DataSpecification};
 use gl::types::*;
 use std::ffi::c_void;
 
 #[derive(Clone)]
 pub struct DataLayout {
     specs: Vec<DataSpecification>,
+    locations: Vec<GLuint>,
+    offsets: Vec<GLuint>,
+    //Set for vertices with padding at the end, e.g. #[repr(C)] structs
+    vertex_stride: Option<GLuint>,
 }
 impl DataLayout {
-    pub fn push(mut self, spec: DataSpecification) -> Self {
+    //Places the attribute right after the previous one, at its own or the next free location
+    pub fn push(self, spec: DataSpecification) -> Self {
+        let location = spec
+            .location
+            .unwrap_or_else(|| self.locations.iter().max().map_or(0, |l| l + 1));
+        let offset = self.end();
+        self.push_at(spec, location, offset)
+    }
+    pub fn push_at(mut self, spec: DataSpecification, location: GLuint, offset: GLuint) -> Self {
+        assert!(
+            !self.locations.contains(&location),
+            "Location {} is already used by the layout",
+            location
+        );
         self.specs.push(spec);
+        self.locations.push(location);
+        self.offsets.push(offset);
+        self
+    }
+    pub fn with_stride(mut self, stride: GLuint) -> Self {
+        debug_assert!(stride >= self.end());
+        self.vertex_stride = Some(stride);
+        self
+    }
+    //Turns every attribute into per-instance data, e.g. for a buffer of instance transforms
+    pub fn with_divisor(mut self, divisor: GLuint) -> Self {
+        for spec in self.specs.iter_mut() {
+            spec.divisor = divisor;
+        }
         self
     }
+    fn end(&self) -> GLuint {
+        self.specs
+            .iter()
+            .zip(self.offsets.iter())
+            .map(|(s, o)| o + s.stride)
+            .max()
+            .unwrap_or(0)
+    }
     pub fn infer_from_f32slice(
         slice: &[f32],
         cutoffs: &[usize],
@@ -22,43 +61,124 @@ impl DataLayout {
         let mut layout = DataLayout::default();
         let mut last_cutoff = 0;
         for c in cutoffs.iter() {
-            let components = (c - last_cutoff) as u32;
-            layout = layout.push(DataSpecification {
-                stride: components * 4,
-                components: components as GLint,
+            layout = layout.push(DataSpecification::new(
+                ComponentType::Float,
+                (c - last_cutoff) as GLint,
                 normalize,
-            });
+                false,
+            ));
             last_cutoff = *c;
         }
-        let components = (cols - last_cutoff) as u32;
-        layout = layout.push(DataSpecification {
-            stride: components * 4,
-            components: components as GLint,
+        layout = layout.push(DataSpecification::new(
+            ComponentType::Float,
+            (cols - last_cutoff) as GLint,
             normalize,
-        });
+            false,
+        ));
         layout
     }
+    pub fn specs(&self) -> &[DataSpecification] {
+        &self.specs
+    }
+    //Size of one vertex in bytes
+    pub fn stride(&self) -> GLuint {
+        self.vertex_stride.unwrap_or_else(|| self.end())
+    }
+    //Byte offset of an attribute inside a vertex
+    pub fn offset(&self, attribute: usize) -> GLuint {
+        self.offsets[attribute]
+    }
+    pub fn location(&self, attribute: usize) -> GLuint {
+        self.locations[attribute]
+    }
+    pub fn locations(&self) -> &[GLuint] {
+        &self.locations
+    }
+    //Components of one float attribute for every vertex in an interleaved f32 buffer
+    pub fn iter_attribute<'a>(
+        &self,
+        data: &'a [f32],
+        attribute: usize,
+    ) -> impl Iterator<Item = &'a [f32]> + 'a {
+        let stride = self.stride() as usize / 4;
+        let offset = self.offset(attribute) as usize / 4;
+        let components = self.specs[attribute].components as usize;
+        debug_assert!(self.specs[attribute].component_type == ComponentType::Float);
+        debug_assert!(self.stride() & 3 == 0 && self.offset(attribute) & 3 == 0);
+        debug_assert!(stride > 0 && data.len() / stride * stride == data.len());
+        data.chunks_exact(stride)
+            .map(move |vertex| &vertex[offset..offset + components])
+    }
     pub fn vertex_attrib_pointer(&self) {
-        let sum_stride = self.specs.iter().fold(0 as GLuint, |c, s| c + s.stride);
-        unsafe {
-            let mut current_offset = 0 as GLuint;
-            for (i, spec) in self.specs.iter().enumerate() {
-                gl::VertexAttribPointer(
-                    i as GLuint,
-                    spec.components,
-                    gl::FLOAT,
-                    spec.normalize,
-                    sum_stride as GLint,
-                    current_offset as *const c_void,
-                );
-                gl::EnableVertexAttribArray(i as GLuint);
-                current_offset += spec.stride;
+        let stride = self.stride() as GLint;
+        for (i, spec) in self.specs.iter().enumerate() {
+            let (location, offset) = (self.locations[i], self.offsets[i] as *const c_void);
+            unsafe {
+                if spec.integer {
+                    gl::VertexAttribIPointer(
+                        location,
+                        spec.components,
+                        spec.component_type.gl_type(),
+                        stride,
+                        offset,
+                    );
+                } else {
+                    gl::VertexAttribPointer(
+                        location,
+                        spec.components,
+                        spec.component_type.gl_type(),
+                        spec.normalize,
+                        stride,
+                        offset,
+                    );
+                }
+                if spec.divisor != 0 {
+                    gl::VertexAttribDivisor(location, spec.divisor);
+                }
+                gl::EnableVertexAttribArray(location);
             }
         }
     }
 }
 impl Default for DataLayout {
     fn default() -> Self {
-        DataLayout { specs: vec![] }
+        DataLayout {
+            specs: vec![],
+            locations: vec![],
+            offsets: vec![],
+            vertex_stride: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_locations_and_divisors() {
+        let layout = DataLayout::default()
+            .push(DataSpecification::float(3))
+            .push(DataSpecification::float(2).with_location(4))
+            .push(DataSpecification::float(3));
+        assert_eq!(layout.locations(), &[0, 4, 5]);
+        assert_eq!(layout.stride(), 32);
+        //Four vec4 columns of a per-instance matrix
+        let mut instances = DataLayout::default();
+        for column in 0..4 {
+            instances = instances.push(DataSpecification::float(4).with_location(6 + column));
+        }
+        let instances = instances.with_divisor(1);
+        assert_eq!(instances.locations(), &[6, 7, 8, 9]);
+        assert_eq!(instances.offset(3), 48);
+        assert!(instances.specs().iter().all(|s| s.divisor == 1));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_duplicate_location() {
+        let _ = DataLayout::default()
+            .push(DataSpecification::float(3).with_location(2))
+            .push(DataSpecification::float(3).with_location(2));
     }
 }
@@ -1,7 +1,151 @@
 use gl::types::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ComponentType {
+    Float,
+    HalfFloat,
+    Byte,
+    UnsignedByte,
+    Short,
+    UnsignedShort,
+    Int,
+    UnsignedInt,
+    //Four components packed into one 32 bit word, w in the top two bits
+    Int2_10_10_10Rev,
+    UnsignedInt2_10_10_10Rev,
+}
+impl ComponentType {
+    pub fn gl_type(self) -> GLenum {
+        match self {
+            ComponentType::Float => gl::FLOAT,
+            ComponentType::HalfFloat => gl::HALF_FLOAT,
+            ComponentType::Byte => gl::BYTE,
+            ComponentType::UnsignedByte => gl::UNSIGNED_BYTE,
+            ComponentType::Short => gl::SHORT,
+            ComponentType::UnsignedShort => gl::UNSIGNED_SHORT,
+            ComponentType::Int => gl::INT,
+            ComponentType::UnsignedInt => gl::UNSIGNED_INT,
+            ComponentType::Int2_10_10_10Rev => gl::INT_2_10_10_10_REV,
+            ComponentType::UnsignedInt2_10_10_10Rev => gl::UNSIGNED_INT_2_10_10_10_REV,
+        }
+    }
+    //Size in bytes of an attribute with the given number of components
+    pub fn size(self, components: GLint) -> GLuint {
+        let component_size = match self {
+            ComponentType::Byte | ComponentType::UnsignedByte => 1,
+            ComponentType::HalfFloat | ComponentType::Short | ComponentType::UnsignedShort => 2,
+            ComponentType::Float | ComponentType::Int | ComponentType::UnsignedInt => 4,
+            ComponentType::Int2_10_10_10Rev | ComponentType::UnsignedInt2_10_10_10Rev => {
+                return 4;
+            }
+        };
+        component_size * components as GLuint
+    }
+    pub fn is_packed(self) -> bool {
+        matches!(
+            self,
+            ComponentType::Int2_10_10_10Rev | ComponentType::UnsignedInt2_10_10_10Rev
+        )
+    }
+    //Types that can be read as ivec/uvec in the shader through glVertexAttribIPointer
+    pub fn is_integer(self) -> bool {
+        matches!(
+            self,
+            ComponentType::Byte
+                | ComponentType::UnsignedByte
+                | ComponentType::Short
+                | ComponentType::UnsignedShort
+                | ComponentType::Int
+                | ComponentType::UnsignedInt
+        )
+    }
+}
+
 #[derive(Clone)]
 pub struct DataSpecification {
     pub stride: GLuint,
     pub components: GLint,
     pub normalize: GLboolean,
-}
\ No newline at end of file
+    pub component_type: ComponentType,
+    //Integer attributes are not converted to float and have to be declared as ivec/uvec
+    pub integer: bool,
+    //Explicit attribute location, otherwise the next free one of the DataLayout
+    pub location: Option<GLuint>,
+    //Advance once per divisor instances instead of once per vertex, 0 for per-vertex data
+    pub divisor: GLuint,
+}
+impl DataSpecification {
+    pub fn new(
+        component_type: ComponentType,
+        components: GLint,
+        normalize: GLboolean,
+        integer: bool,
+    ) -> Self {
+        debug_assert!((1..=4).contains(&components));
+        debug_assert!(!component_type.is_packed() || components == 4);
+        debug_assert!(!integer || (component_type.is_integer() && normalize == gl::FALSE));
+        DataSpecification {
+            stride: component_type.size(components),
+            components,
+            normalize,
+            component_type,
+            integer,
+            location: None,
+            divisor: 0,
+        }
+    }
+    pub fn with_location(mut self, location: GLuint) -> Self {
+        self.location = Some(location);
+        self
+    }
+    pub fn with_divisor(mut self, divisor: GLuint) -> Self {
+        self.divisor = divisor;
+        self
+    }
+    pub fn float(components: GLint) -> Self {
+        DataSpecification::new(ComponentType::Float, components, gl::FALSE, false)
+    }
+    pub fn half_float(components: GLint) -> Self {
+        DataSpecification::new(ComponentType::HalfFloat, components, gl::FALSE, false)
+    }
+    //Integers mapped to [0, 1] or [-1, 1] in the shader, e.g. u8 colours or i16 normals
+    pub fn normalized(component_type: ComponentType, components: GLint) -> Self {
+        DataSpecification::new(component_type, components, gl::TRUE, false)
+    }
+    pub fn integer(component_type: ComponentType, components: GLint) -> Self {
+        DataSpecification::new(component_type, components, gl::FALSE, true)
+    }
+    pub fn packed(signed: bool, normalize: GLboolean) -> Self {
+        let component_type = if signed {
+            ComponentType::Int2_10_10_10Rev
+        } else {
+            ComponentType::UnsignedInt2_10_10_10Rev
+        };
+        DataSpecification::new(component_type, 4, normalize, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_specification_sizes() {
+        assert_eq!(DataSpecification::float(3).stride, 12);
+        assert_eq!(DataSpecification::half_float(2).stride, 4);
+        let colour = DataSpecification::normalized(ComponentType::UnsignedByte, 4);
+        assert_eq!(colour.stride, 4);
+        assert_eq!(colour.normalize, gl::TRUE);
+        assert_eq!(
+            DataSpecification::normalized(ComponentType::Short, 3).stride,
+            6
+        );
+        let id = DataSpecification::integer(ComponentType::UnsignedShort, 1);
+        assert!(id.integer && id.stride == 2);
+        let normal = DataSpecification::packed(true, gl::TRUE);
+        assert_eq!(normal.stride, 4);
+        assert_eq!(normal.component_type.gl_type(), gl::INT_2_10_10_10_REV);
+        let instance = DataSpecification::float(4).with_location(3).with_divisor(1);
+        assert_eq!((instance.location, instance.divisor), (Some(3), 1));
+    }
+}
@@ -1,2 +1,6 @@
+pub mod block_layout;
 pub mod data_layout;
-pub mod data_specification;
\ No newline at end of file
+pub mod data_specification;
+pub mod packing;
+pub mod vertex;
+pub mod vertex_data;
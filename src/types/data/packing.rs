@@ -0,0 +1,155 @@
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+
+//Conversions from f32 into the compact vertex formats of ComponentType
+
+//Plain data that can be viewed as bytes and read back from them:
+//no padding, no pointers and every bit pattern is a valid value.
+//Safety: only implement for #[repr(C)] or #[repr(transparent)] types of Pod fields without padding.
+#[allow(clippy::missing_safety_doc)]
+pub unsafe trait Pod: Copy {}
+macro_rules! impl_pod {
+    ($($t: ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+unsafe impl<T: Pod> Pod for Vec2<T> {}
+unsafe impl<T: Pod> Pod for Vec3<T> {}
+unsafe impl<T: Pod> Pod for Vec4<T> {}
+
+//Raw bytes of a slice of plain data, e.g. to upload vertex structs or mixed buffers
+pub fn as_bytes<T: Pod>(data: &[T]) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data)) }
+}
+
+//IEEE 754 binary16, rounded to nearest even
+pub fn to_half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+    if exponent == 0xff {
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    let round = |value: u32, remainder: u32, halfway: u32| {
+        if remainder > halfway || (remainder == halfway && value & 1 == 1) {
+            value + 1
+        } else {
+            value
+        }
+    };
+    if exponent <= 0 {
+        //Subnormal, or too small and flushed to zero
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - exponent) as u32;
+        let half = mantissa >> shift;
+        let remainder = mantissa & ((1 << shift) - 1);
+        return sign | round(half, remainder, 1 << (shift - 1)) as u16;
+    }
+    //A carry out of the mantissa correctly rounds up into the exponent
+    let half = ((exponent as u32) << 10) | (mantissa >> 13);
+    sign | round(half, mantissa & 0x1fff, 0x1000) as u16
+}
+pub fn from_half(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x3ff) as u32;
+    match exponent {
+        0 => {
+            let value = mantissa as f32 * 5.960_464_5e-8;
+            if sign == 0 {
+                value
+            } else {
+                -value
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
+
+//Fixed point values read back by the shader as c / (2^b - 1), or c / (2^(b - 1) - 1) if signed
+pub fn to_unorm8(value: f32) -> u8 {
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
+pub fn to_unorm16(value: f32) -> u16 {
+    (value.clamp(0., 1.) * 65535.).round() as u16
+}
+pub fn to_snorm8(value: f32) -> i8 {
+    (value.clamp(-1., 1.) * 127.).round() as i8
+}
+pub fn to_snorm16(value: f32) -> i16 {
+    (value.clamp(-1., 1.) * 32767.).round() as i16
+}
+
+//For ComponentType::Int2_10_10_10Rev with normalize, x in the lowest bits
+pub fn pack_snorm_2_10_10_10(x: f32, y: f32, z: f32, w: f32) -> u32 {
+    let component = |value: f32, max: f32, mask: u32| {
+        ((value.clamp(-1., 1.) * max).round() as i32 as u32) & mask
+    };
+    component(x, 511., 0x3ff)
+        | component(y, 511., 0x3ff) << 10
+        | component(z, 511., 0x3ff) << 20
+        | component(w, 1., 0x3) << 30
+}
+//For ComponentType::UnsignedInt2_10_10_10Rev with normalize
+pub fn pack_unorm_2_10_10_10(x: f32, y: f32, z: f32, w: f32) -> u32 {
+    let component = |value: f32, max: f32| (value.clamp(0., 1.) * max).round() as u32;
+    component(x, 1023.)
+        | component(y, 1023.) << 10
+        | component(z, 1023.) << 20
+        | component(w, 3.) << 30
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_half_floats() {
+        assert_eq!(to_half(0.), 0);
+        assert_eq!(to_half(-0.), 0x8000);
+        assert_eq!(to_half(1.), 0x3c00);
+        assert_eq!(to_half(-2.), 0xc000);
+        assert_eq!(to_half(65504.), 0x7bff);
+        assert_eq!(to_half(1e6), 0x7c00);
+        assert_eq!(to_half(f32::NEG_INFINITY), 0xfc00);
+        assert!(from_half(to_half(f32::NAN)).is_nan());
+        //Smallest subnormal and rounding to nearest even
+        assert_eq!(to_half(5.960_464_5e-8), 1);
+        assert_eq!(to_half(1. + 1. / 2048.), 0x3c00);
+        assert_eq!(to_half(1. + 3. / 2048.), 0x3c02);
+        for &value in [0.1f32, -3.75, 1000.5, 6.1e-5, 3e-7, 0.333].iter() {
+            let back = from_half(to_half(value));
+            assert!((back - value).abs() <= value.abs() / 1024. + 6e-8);
+        }
+        assert_eq!(from_half(0x3555), 0.333_251_95);
+        assert_eq!(from_half(0x8001), -5.960_464_5e-8);
+    }
+
+    #[test]
+    pub fn test_normalized_packing() {
+        assert_eq!(to_unorm8(1.), 255);
+        assert_eq!(to_unorm8(0.5), 128);
+        assert_eq!(to_unorm8(-3.), 0);
+        assert_eq!(to_unorm16(1.), 65535);
+        assert_eq!(to_snorm8(-1.), -127);
+        assert_eq!(to_snorm16(0.5), 16384);
+        assert_eq!(
+            pack_unorm_2_10_10_10(1., 0., 1., 1.),
+            0xc000_0000 | 0x3ff << 20 | 0x3ff
+        );
+        assert_eq!(pack_snorm_2_10_10_10(0., 0., 1., 0.), 511 << 20);
+        assert_eq!(pack_snorm_2_10_10_10(-1., 0., 0., -1.), 0x201 | 0b11 << 30);
+        assert_eq!(as_bytes(&[1u16, 0x0203]), &[1, 0, 3, 2]);
+    }
+}
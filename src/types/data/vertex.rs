@@ -0,0 +1,167 @@
+use crate::types::data::data_layout::DataLayout;
+use crate::types::data::data_specification::{ComponentType, DataSpecification};
+use crate::types::data::packing::{to_half, Pod};
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+use gl::types::*;
+pub use vertex_derive::Vertex;
+
+//Vertex structs with a known layout, usually implemented through #[derive(Vertex)]
+pub trait Vertex: Pod {
+    fn data_layout() -> DataLayout;
+}
+//Field types that map onto a single vertex attribute
+pub trait VertexAttribute: Pod {
+    const COMPONENT_TYPE: ComponentType;
+    const COMPONENTS: GLint;
+    fn specification(normalize: bool, integer: bool) -> DataSpecification {
+        let normalize = if normalize { gl::TRUE } else { gl::FALSE };
+        DataSpecification::new(Self::COMPONENT_TYPE, Self::COMPONENTS, normalize, integer)
+    }
+}
+
+//Half-float component, use it in place of f32 fields
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[repr(transparent)]
+pub struct Half(pub u16);
+impl From<f32> for Half {
+    fn from(value: f32) -> Self {
+        Half(to_half(value))
+    }
+}
+//Four components packed into one word, see packing::pack_snorm_2_10_10_10
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[repr(transparent)]
+pub struct Int2_10_10_10Rev(pub u32);
+//See packing::pack_unorm_2_10_10_10
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[repr(transparent)]
+pub struct UnsignedInt2_10_10_10Rev(pub u32);
+unsafe impl Pod for Half {}
+unsafe impl Pod for Int2_10_10_10Rev {}
+unsafe impl Pod for UnsignedInt2_10_10_10Rev {}
+
+macro_rules! impl_vertex_attribute {
+    ($t: ty, $component_type: expr) => {
+        impl VertexAttribute for $t {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 1;
+        }
+        impl VertexAttribute for [$t; 2] {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 2;
+        }
+        impl VertexAttribute for [$t; 3] {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 3;
+        }
+        impl VertexAttribute for [$t; 4] {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 4;
+        }
+        impl VertexAttribute for Vec2<$t> {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 2;
+        }
+        impl VertexAttribute for Vec3<$t> {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 3;
+        }
+        impl VertexAttribute for Vec4<$t> {
+            const COMPONENT_TYPE: ComponentType = $component_type;
+            const COMPONENTS: GLint = 4;
+        }
+    };
+}
+impl_vertex_attribute!(f32, ComponentType::Float);
+impl_vertex_attribute!(Half, ComponentType::HalfFloat);
+impl_vertex_attribute!(i8, ComponentType::Byte);
+impl_vertex_attribute!(u8, ComponentType::UnsignedByte);
+impl_vertex_attribute!(i16, ComponentType::Short);
+impl_vertex_attribute!(u16, ComponentType::UnsignedShort);
+impl_vertex_attribute!(i32, ComponentType::Int);
+impl_vertex_attribute!(u32, ComponentType::UnsignedInt);
+impl VertexAttribute for Int2_10_10_10Rev {
+    const COMPONENT_TYPE: ComponentType = ComponentType::Int2_10_10_10Rev;
+    const COMPONENTS: GLint = 4;
+}
+impl VertexAttribute for UnsignedInt2_10_10_10Rev {
+    const COMPONENT_TYPE: ComponentType = ComponentType::UnsignedInt2_10_10_10Rev;
+    const COMPONENTS: GLint = 4;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::data::packing::{as_bytes, pack_snorm_2_10_10_10};
+
+    #[derive(Clone, Copy, Vertex)]
+    #[repr(C)]
+    struct ColoredVertex {
+        position: Vec3<f32>,
+        #[vertex(normalize)]
+        color: [u8; 4],
+        uv: [Half; 2],
+        #[vertex(location = 5)]
+        normal: Int2_10_10_10Rev,
+        #[vertex(integer)]
+        material: u16,
+        #[vertex(skip)]
+        _padding: [u8; 2],
+    }
+
+    #[test]
+    pub fn test_derive_vertex() {
+        let layout = ColoredVertex::data_layout();
+        assert_eq!(layout.specs().len(), 5);
+        assert_eq!(
+            layout.stride() as usize,
+            std::mem::size_of::<ColoredVertex>()
+        );
+        assert_eq!(layout.stride(), 28);
+        let offsets: Vec<GLuint> = (0..5).map(|i| layout.offset(i)).collect();
+        assert_eq!(offsets, vec![0, 12, 16, 20, 24]);
+        let locations: Vec<GLuint> = (0..5).map(|i| layout.location(i)).collect();
+        assert_eq!(locations, vec![0, 1, 2, 5, 6]);
+        let specs = layout.specs();
+        assert_eq!(specs[0].component_type, ComponentType::Float);
+        assert_eq!(specs[1].normalize, gl::TRUE);
+        assert_eq!(specs[2].component_type, ComponentType::HalfFloat);
+        assert_eq!(specs[3].components, 4);
+        assert!(specs[4].integer);
+
+        let vertices = [ColoredVertex {
+            position: Vec3::new(1., 2., 3.),
+            color: [255, 0, 0, 255],
+            uv: [Half::from(0.5), Half::from(1.)],
+            normal: Int2_10_10_10Rev(pack_snorm_2_10_10_10(0., 0., 1., 0.)),
+            material: 3,
+            _padding: [0; 2],
+        }];
+        let bytes = as_bytes(&vertices);
+        assert_eq!(bytes.len(), 28);
+        assert_eq!(&bytes[12..16], &[255, 0, 0, 255]);
+        assert_eq!(&bytes[24..26], &3u16.to_ne_bytes());
+    }
+
+    #[derive(Clone, Copy, Vertex)]
+    #[repr(C)]
+    struct Instance {
+        #[vertex(location = 3, divisor = 1)]
+        offset: [f32; 3],
+        #[vertex(normalize, divisor = 2)]
+        tint: [u8; 4],
+        #[vertex(location = 1)]
+        scale: f32,
+        layer: f32,
+    }
+
+    #[test]
+    pub fn test_derive_instance() {
+        let layout = Instance::data_layout();
+        //layer continues after the largest location, not after scale
+        assert_eq!(layout.locations(), &[3, 4, 1, 5]);
+        assert_eq!(layout.specs()[0].divisor, 1);
+        assert_eq!(layout.specs()[1].divisor, 2);
+        assert_eq!(layout.stride(), 24);
+    }
+}
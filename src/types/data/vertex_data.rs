@@ -0,0 +1,67 @@
+use crate::types::data::data_layout::DataLayout;
+use crate::types::data::packing::{as_bytes, Pod};
+
+//Interleaved vertices of mixed component types, pushed attribute by attribute in layout order
+#[derive(Clone, Default)]
+pub struct VertexData {
+    bytes: Vec<u8>,
+}
+impl VertexData {
+    pub fn push<T: Pod>(&mut self, value: T) -> &mut Self {
+        self.push_slice(&[value])
+    }
+    pub fn push_slice<T: Pod>(&mut self, values: &[T]) -> &mut Self {
+        self.bytes.extend_from_slice(as_bytes(values));
+        self
+    }
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+    pub fn vertex_count(&self, layout: &DataLayout) -> usize {
+        let stride = layout.stride() as usize;
+        debug_assert!(self.bytes.len() / stride * stride == self.bytes.len());
+        self.bytes.len() / stride
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::data::data_specification::{ComponentType, DataSpecification};
+    use crate::types::data::packing::{pack_snorm_2_10_10_10, to_half, to_unorm8};
+
+    #[test]
+    pub fn test_interleaved_vertices() {
+        let layout = DataLayout::default()
+            .push(DataSpecification::float(3))
+            .push(DataSpecification::packed(true, gl::TRUE))
+            .push(DataSpecification::normalized(
+                ComponentType::UnsignedByte,
+                4,
+            ))
+            .push(DataSpecification::half_float(2))
+            .push(DataSpecification::integer(ComponentType::UnsignedShort, 2));
+        assert_eq!(layout.stride(), 12 + 4 + 4 + 4 + 4);
+        assert_eq!(layout.offset(3), 20);
+        let mut data = VertexData::default();
+        for i in 0..3 {
+            data.push_slice(&[i as f32, 0., 1.])
+                .push(pack_snorm_2_10_10_10(0., 1., 0., 0.))
+                .push_slice(&[to_unorm8(1.), 0, 0, 255])
+                .push_slice(&[to_half(0.5), to_half(1.)])
+                .push_slice(&[i as u16, 7]);
+        }
+        assert_eq!(data.len(), 84);
+        assert_eq!(data.vertex_count(&layout), 3);
+        let second = &data.as_bytes()[28..56];
+        assert_eq!(&second[0..4], &1f32.to_ne_bytes());
+        assert_eq!(&second[16..20], &[255, 0, 0, 255]);
+        assert_eq!(&second[24..26], &1u16.to_ne_bytes());
+    }
+}
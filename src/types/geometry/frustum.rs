@@ -0,0 +1,150 @@
+use crate::types::geometry::plane::Plane;
+use crate::types::linalg::bounding_volume::{Aabb, BoundingSphere};
+use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::matrix::Matrix;
+use crate::types::linalg::vector::Vec3;
+
+//Six planes with normals pointing inside: left, right, bottom, top, near, far
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+//Bounds that can be tested against a frustum, conservative: may report invisible bounds as visible
+pub trait Cullable {
+    fn is_visible(&self, frustum: &Frustum) -> bool;
+}
+impl Frustum {
+    pub fn new(planes: [Plane; 6]) -> Self {
+        Frustum { planes }
+    }
+    //Gribb–Hartmann extraction, points with -w <= x, y, z <= w are inside
+    pub fn from_view_projection(view_projection: &Matrix<f32>) -> Self {
+        debug_assert!(view_projection.dimension == Dimension::new(4, 4));
+        let row = |r: usize| {
+            let row = view_projection.row(r);
+            [row[0], row[1], row[2], row[3]]
+        };
+        let w = row(3);
+        let plane = |r: usize, sign: f32| {
+            let v = row(r);
+            Plane::new(
+                Vec3::new(w[0] + sign * v[0], w[1] + sign * v[1], w[2] + sign * v[2]),
+                w[3] + sign * v[3],
+            )
+            .normalize()
+        };
+        Frustum::new([
+            plane(0, 1.),
+            plane(0, -1.),
+            plane(1, 1.),
+            plane(1, -1.),
+            plane(2, 1.),
+            plane(2, -1.),
+        ])
+    }
+    pub fn contains_point(&self, point: Vec3<f32>) -> bool {
+        self.planes.iter().all(|p| p.signed_distance(point) >= 0.)
+    }
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        self.planes
+            .iter()
+            .all(|p| p.signed_distance(sphere.center) >= -sphere.radius)
+    }
+    //Rejects the box if its corner furthest along a plane normal is outside that plane
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let pick = |positive: bool, min: f32, max: f32| if positive { max } else { min };
+        self.planes.iter().all(|p| {
+            let corner = Vec3::new(
+                pick(p.normal.x >= 0., aabb.min.x, aabb.max.x),
+                pick(p.normal.y >= 0., aabb.min.y, aabb.max.y),
+                pick(p.normal.z >= 0., aabb.min.z, aabb.max.z),
+            );
+            p.signed_distance(corner) >= 0.
+        })
+    }
+    //Indices of the visible bounds, in order
+    pub fn cull<B: Cullable>(&self, bounds: &[B]) -> Vec<usize> {
+        let mut visible = Vec::new();
+        self.cull_into(bounds, &mut visible);
+        visible
+    }
+    pub fn cull_into<B: Cullable>(&self, bounds: &[B], visible: &mut Vec<usize>) {
+        visible.clear();
+        visible.extend(
+            bounds
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.is_visible(self))
+                .map(|(i, _)| i),
+        );
+    }
+}
+impl Cullable for Aabb {
+    fn is_visible(&self, frustum: &Frustum) -> bool {
+        frustum.intersects_aabb(self)
+    }
+}
+impl Cullable for BoundingSphere {
+    fn is_visible(&self, frustum: &Frustum) -> bool {
+        frustum.intersects_sphere(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Frustum {
+        let view_projection = Matrix::identity4()
+            .perspective4(90f32.to_radians(), 1., 1., 100.)
+            .look_at4([0., 0., 0.], [0., 0., -1.], [0., 1., 0.]);
+        Frustum::from_view_projection(&view_projection)
+    }
+
+    #[test]
+    pub fn test_frustum_planes() {
+        let frustum = camera();
+        let near = frustum.planes[4];
+        assert!((near.normal - Vec3::new(0., 0., -1.)).length() < 1e-5);
+        assert!((near.distance + 1.).abs() < 1e-5);
+        let far = frustum.planes[5];
+        assert!((far.normal - Vec3::new(0., 0., 1.)).length() < 1e-5);
+        assert!((far.distance - 100.).abs() < 1e-3);
+        let left = frustum.planes[0];
+        let diagonal = 0.5f32.sqrt();
+        assert!((left.normal - Vec3::new(diagonal, 0., -diagonal)).length() < 1e-5);
+        assert!(left.distance.abs() < 1e-5);
+        assert!(frustum.contains_point(Vec3::new(0., 0., -10.)));
+        assert!(frustum.contains_point(Vec3::new(9., -9., -10.)));
+        assert!(!frustum.contains_point(Vec3::new(11., 0., -10.)));
+        assert!(!frustum.contains_point(Vec3::new(0., 0., -0.5)));
+        assert!(!frustum.contains_point(Vec3::new(0., 0., 10.)));
+    }
+
+    #[test]
+    pub fn test_culling() {
+        let frustum = camera();
+        let spheres = [
+            BoundingSphere::new(Vec3::new(0., 0., -50.), 1.),
+            BoundingSphere::new(Vec3::new(0., 0., 50.), 1.),
+            BoundingSphere::new(Vec3::new(12., 0., -10.), 1.),
+            BoundingSphere::new(Vec3::new(12., 0., -10.), 1.5),
+            BoundingSphere::new(Vec3::new(0., 0., -101.), 2.),
+        ];
+        assert_eq!(frustum.cull(&spheres), vec![0, 3, 4]);
+        let boxes = [
+            Aabb::new(Vec3::new(-1., -1., -11.), Vec3::new(1., 1., -9.)),
+            Aabb::new(Vec3::new(-1., -1., 1.), Vec3::new(1., 1., 3.)),
+            Aabb::new(Vec3::new(11.5, -1., -11.), Vec3::new(12., 1., -9.)),
+            Aabb::new(Vec3::new(9.5, -1., -11.), Vec3::new(12., 1., -9.)),
+            Aabb::new(Vec3::new(-100., -100., -0.5), Vec3::new(100., 100., 0.5)),
+        ];
+        let mut visible = vec![42];
+        frustum.cull_into(&boxes, &mut visible);
+        assert_eq!(visible, vec![0, 3]);
+        assert!(frustum.intersects_aabb(&Aabb::new(
+            Vec3::new(-100., -100., -2.),
+            Vec3::new(100., 100., -1.5)
+        )));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod frustum;
+pub mod plane;
+pub mod ray;
+pub mod spline;
+pub mod triangle;
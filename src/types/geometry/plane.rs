@@ -0,0 +1,36 @@
+use crate::types::linalg::vector::Vec3;
+
+//Points p on the plane satisfy normal.dot(p) + distance == 0, the normal points to the positive side
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Plane {
+    pub normal: Vec3<f32>,
+    pub distance: f32,
+}
+impl Plane {
+    pub fn new(normal: Vec3<f32>, distance: f32) -> Self {
+        Plane { normal, distance }
+    }
+    pub fn from_point_normal(point: Vec3<f32>, normal: Vec3<f32>) -> Self {
+        let normal = normal.normalize();
+        Plane::new(normal, -normal.dot(point))
+    }
+    //Counter-clockwise points face the normal, None if they are collinear
+    pub fn from_points(a: Vec3<f32>, b: Vec3<f32>, c: Vec3<f32>) -> Option<Self> {
+        let normal = (b - a).cross(c - a);
+        if normal.length_squared() <= f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+        Some(Plane::from_point_normal(a, normal))
+    }
+    //Scales the equation so that the normal has unit length
+    pub fn normalize(self) -> Plane {
+        let inv_length = 1. / self.normal.length();
+        Plane::new(self.normal * inv_length, self.distance * inv_length)
+    }
+    pub fn signed_distance(&self, point: Vec3<f32>) -> f32 {
+        self.normal.dot(point) + self.distance
+    }
+    pub fn project(&self, point: Vec3<f32>) -> Vec3<f32> {
+        point - self.normal * (self.signed_distance(point) / self.normal.length_squared())
+    }
+}
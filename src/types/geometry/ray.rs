@@ -0,0 +1,227 @@
+use crate::types::geometry::plane::Plane;
+use crate::types::geometry::triangle::Triangle;
+use crate::types::linalg::bounding_volume::{Aabb, BoundingSphere};
+use crate::types::linalg::matrix::Matrix;
+use crate::types::linalg::vector::Vec3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ray {
+    pub origin: Vec3<f32>,
+    pub direction: Vec3<f32>,
+}
+//Distance along the ray and the weights of the triangle's a, b and c at the hit point
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TriangleHit {
+    pub distance: f32,
+    pub barycentric: Vec3<f32>,
+}
+impl Ray {
+    pub fn new(origin: Vec3<f32>, direction: Vec3<f32>) -> Self {
+        Ray {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+    //Screen coordinates in pixels with the origin in the top left corner
+    pub fn from_screen(
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        inverse_view_projection: &Matrix<f32>,
+    ) -> Ray {
+        let ndc_x = 2. * x / width - 1.;
+        let ndc_y = 1. - 2. * y / height;
+        Ray::from_ndc(ndc_x, ndc_y, inverse_view_projection)
+    }
+    pub fn from_ndc(x: f32, y: f32, inverse_view_projection: &Matrix<f32>) -> Ray {
+        let near = inverse_view_projection.transform_point(Vec3::new(x, y, -1.));
+        let far = inverse_view_projection.transform_point(Vec3::new(x, y, 1.));
+        Ray::new(near, far - near)
+    }
+    pub fn at(&self, distance: f32) -> Vec3<f32> {
+        self.origin + self.direction * distance
+    }
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let denominator = plane.normal.dot(self.direction);
+        //Relative to the lengths, so planes from Plane::new with unnormalized normals behave the same
+        if denominator.abs() <= f32::EPSILON * plane.normal.length() * self.direction.length() {
+            return None;
+        }
+        let distance = -plane.signed_distance(self.origin) / denominator;
+        if distance >= 0. {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+    //Möller–Trumbore, hits both faces of the triangle
+    pub fn intersect_triangle(&self, triangle: &Triangle) -> Option<TriangleHit> {
+        let e1 = triangle.b - triangle.a;
+        let e2 = triangle.c - triangle.a;
+        let p = self.direction.cross(e2);
+        let determinant = e1.dot(p);
+        //Relative to the edge lengths, so small triangles aren't taken for parallel ones
+        if determinant.abs() <= f32::EPSILON * self.direction.length() * e1.length() * e2.length() {
+            return None;
+        }
+        let inv_determinant = 1. / determinant;
+        let s = self.origin - triangle.a;
+        let u = s.dot(p) * inv_determinant;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        let q = s.cross(e1);
+        let v = self.direction.dot(q) * inv_determinant;
+        if v < 0. || u + v > 1. {
+            return None;
+        }
+        let distance = e2.dot(q) * inv_determinant;
+        if distance < 0. {
+            return None;
+        }
+        Some(TriangleHit {
+            distance,
+            barycentric: Vec3::new(1. - u - v, u, v),
+        })
+    }
+    //Slab test, the distance is 0 if the origin lies inside the box
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let origin = self.origin.to_array();
+        let direction = self.direction.to_array();
+        let (min, max) = (aabb.min.to_array(), aabb.max.to_array());
+        let mut near = 0f32;
+        let mut far = f32::INFINITY;
+        for i in 0..3 {
+            if direction[i] == 0. {
+                if origin[i] < min[i] || origin[i] > max[i] {
+                    return None;
+                }
+                continue;
+            }
+            let inv_direction = 1. / direction[i];
+            let t1 = (min[i] - origin[i]) * inv_direction;
+            let t2 = (max[i] - origin[i]) * inv_direction;
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+            if near > far {
+                return None;
+            }
+        }
+        Some(near)
+    }
+    //The distance is 0 if the origin lies inside the sphere
+    pub fn intersect_sphere(&self, sphere: &BoundingSphere) -> Option<f32> {
+        let offset = self.origin - sphere.center;
+        let b = offset.dot(self.direction);
+        let c = offset.length_squared() - sphere.radius * sphere.radius;
+        if c <= 0. {
+            return Some(0.);
+        }
+        let discriminant = b * b - c;
+        if b > 0. || discriminant < 0. {
+            return None;
+        }
+        Some(-b - discriminant.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    pub fn test_ray_intersections() {
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.), Vec3::new(0., 0., -2.));
+        assert_eq!(ray.direction, Vec3::new(0., 0., -1.));
+        let triangle = Triangle::new(
+            Vec3::new(0., 0., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 1., 0.),
+        );
+        let hit = ray.intersect_triangle(&triangle).unwrap();
+        assert_eq!(hit.distance, 5.);
+        assert_eq!(hit.barycentric, Vec3::new(0.5, 0.25, 0.25));
+        assert_eq!(
+            triangle.from_barycentric(hit.barycentric),
+            ray.at(hit.distance)
+        );
+        let miss = Ray::new(Vec3::new(0.75, 0.75, 5.), Vec3::new(0., 0., -1.));
+        assert!(miss.intersect_triangle(&triangle).is_none());
+        let behind = Ray::new(Vec3::new(0.25, 0.25, 5.), Vec3::new(0., 0., 1.));
+        assert!(behind.intersect_triangle(&triangle).is_none());
+
+        let plane = Plane::from_point_normal(Vec3::new(0., 0., 1.), Vec3::new(0., 0., 1.));
+        assert_eq!(ray.intersect_plane(&plane), Some(4.));
+        assert_eq!(behind.intersect_plane(&plane), None);
+        let parallel = Ray::new(Vec3::new(0., 0., 5.), Vec3::new(1., 0., 0.));
+        assert_eq!(parallel.intersect_plane(&plane), None);
+
+        let aabb = Aabb::new(Vec3::new(-1., -1., -1.), Vec3::new(1., 1., 1.));
+        assert_eq!(ray.intersect_aabb(&aabb), Some(4.));
+        assert_eq!(behind.intersect_aabb(&aabb), None);
+        assert_eq!(parallel.intersect_aabb(&aabb), None);
+        let inside = Ray::new(Vec3::new(0., 0., 0.), Vec3::new(1., 1., 0.));
+        assert_eq!(inside.intersect_aabb(&aabb), Some(0.));
+        let diagonal = Ray::new(Vec3::new(-3., -3., 0.), Vec3::new(1., 1., 0.));
+        assert!(close(
+            diagonal.intersect_aabb(&aabb).unwrap(),
+            2. * 2f32.sqrt()
+        ));
+
+        let sphere = BoundingSphere::new(Vec3::new(0., 0., 1.), 2.);
+        assert!(close(
+            ray.intersect_sphere(&sphere).unwrap(),
+            4. - 3.875f32.sqrt()
+        ));
+        assert_eq!(behind.intersect_sphere(&sphere), None);
+        assert_eq!(inside.intersect_sphere(&sphere), Some(0.));
+        assert!(parallel.intersect_sphere(&sphere).is_none());
+    }
+
+    #[test]
+    pub fn test_ray_small_triangle() {
+        //Edges of 1e-4, the determinant is 1e-8 which lies below f32::EPSILON
+        let triangle = Triangle::new(
+            Vec3::new(0., 0., 0.),
+            Vec3::new(1e-4, 0., 0.),
+            Vec3::new(0., 1e-4, 0.),
+        );
+        let ray = Ray::new(Vec3::new(2.5e-5, 2.5e-5, 1.), Vec3::new(0., 0., -1.));
+        let hit = ray.intersect_triangle(&triangle).unwrap();
+        assert!(close(hit.distance, 1.));
+        assert!(close(hit.barycentric.x, 0.5));
+        let parallel = Ray::new(Vec3::new(2.5e-5, -1., 0.), Vec3::new(0., 1., 0.));
+        assert!(parallel.intersect_triangle(&triangle).is_none());
+
+        let plane = Plane::new(Vec3::new(0., 0., 1e-8), 0.);
+        assert!(close(ray.intersect_plane(&plane).unwrap(), 1.));
+    }
+
+    #[test]
+    pub fn test_ray_from_screen() {
+        let view_projection = Matrix::identity4()
+            .perspective4(90f32.to_radians(), 2., 0.1, 100.)
+            .look_at4([0., 0., 5.], [0., 0., 0.], [0., 1., 0.]);
+        let inverse = view_projection.inverse4().unwrap();
+        let center = Ray::from_screen(400., 200., 800., 400., &inverse);
+        assert!(close(center.origin.z, 4.9));
+        assert!(close(center.direction.z, -1.));
+        //With a 90 degree vertical fov the top edge of the screen is at 45 degrees
+        let top = Ray::from_screen(400., 0., 800., 400., &inverse);
+        assert!(close(top.direction.y, top.direction.z.abs()));
+        assert!(top.direction.y > 0.);
+        let right = Ray::from_screen(800., 200., 800., 400., &inverse);
+        assert!(close(right.direction.x, 2. * right.direction.z.abs()));
+        let picked = Ray::from_screen(600., 100., 800., 400., &inverse);
+        let point = picked.at(picked
+            .intersect_plane(&Plane::new(Vec3::new(0., 0., 1.), 0.))
+            .unwrap());
+        let projected = view_projection.transform_point(point);
+        assert!(close(projected.x, 0.5) && close(projected.y, 0.5));
+    }
+}
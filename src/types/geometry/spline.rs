@@ -0,0 +1,281 @@
+use crate::types::data::data_layout::DataLayout;
+use crate::types::data::data_specification::DataSpecification;
+use crate::types::linalg::vector::Vec3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SplineKind {
+    //Segments share end points: 3n + 1 control points for n segments
+    Bezier,
+    //Passes through every control point but the first and last one
+    CatmullRom,
+    //Uniform cubic B-spline, C2 continuous but doesn't interpolate the control points
+    BSpline,
+}
+//Piecewise cubic curve, evaluated with a parameter t in [0, 1] over the whole spline
+#[derive(Clone, PartialEq, Debug)]
+pub struct Spline {
+    pub kind: SplineKind,
+    pub points: Vec<Vec3<f32>>,
+}
+//Cumulative length at evenly spaced parameters, maps distances back to parameters
+#[derive(Clone, PartialEq, Debug)]
+pub struct ArcLengthTable {
+    lengths: Vec<f32>,
+}
+
+impl SplineKind {
+    fn weights(self, u: f32) -> [f32; 4] {
+        let (u2, u3, v) = (u * u, u * u * u, 1. - u);
+        match self {
+            SplineKind::Bezier => [v * v * v, 3. * u * v * v, 3. * u2 * v, u3],
+            SplineKind::CatmullRom => [
+                0.5 * (-u3 + 2. * u2 - u),
+                0.5 * (3. * u3 - 5. * u2 + 2.),
+                0.5 * (-3. * u3 + 4. * u2 + u),
+                0.5 * (u3 - u2),
+            ],
+            SplineKind::BSpline => [
+                v * v * v / 6.,
+                (3. * u3 - 6. * u2 + 4.) / 6.,
+                (-3. * u3 + 3. * u2 + 3. * u + 1.) / 6.,
+                u3 / 6.,
+            ],
+        }
+    }
+    fn derivative_weights(self, u: f32) -> [f32; 4] {
+        let (u2, v) = (u * u, 1. - u);
+        match self {
+            SplineKind::Bezier => [
+                -3. * v * v,
+                3. * v * v - 6. * u * v,
+                6. * u * v - 3. * u2,
+                3. * u2,
+            ],
+            SplineKind::CatmullRom => [
+                0.5 * (-3. * u2 + 4. * u - 1.),
+                0.5 * (9. * u2 - 10. * u),
+                0.5 * (-9. * u2 + 8. * u + 1.),
+                0.5 * (3. * u2 - 2. * u),
+            ],
+            SplineKind::BSpline => [
+                -0.5 * v * v,
+                (9. * u2 - 12. * u) / 6.,
+                (-9. * u2 + 6. * u + 3.) / 6.,
+                0.5 * u2,
+            ],
+        }
+    }
+    //Distance between the first control points of consecutive segments
+    fn step(self) -> usize {
+        match self {
+            SplineKind::Bezier => 3,
+            SplineKind::CatmullRom | SplineKind::BSpline => 1,
+        }
+    }
+}
+
+impl Spline {
+    pub fn new(kind: SplineKind, points: Vec<Vec3<f32>>) -> Self {
+        let spline = Spline { kind, points };
+        //The last segment has to end exactly on the last control point
+        let len = spline.points.len();
+        assert!(len >= 4 && (spline.segments() - 1) * kind.step() + 4 == len);
+        spline
+    }
+    pub fn bezier(points: Vec<Vec3<f32>>) -> Self {
+        Spline::new(SplineKind::Bezier, points)
+    }
+    pub fn catmull_rom(points: Vec<Vec3<f32>>) -> Self {
+        Spline::new(SplineKind::CatmullRom, points)
+    }
+    pub fn bspline(points: Vec<Vec3<f32>>) -> Self {
+        Spline::new(SplineKind::BSpline, points)
+    }
+    pub fn segments(&self) -> usize {
+        match self.kind {
+            SplineKind::Bezier => (self.points.len() - 1) / 3,
+            SplineKind::CatmullRom | SplineKind::BSpline => self.points.len() - 3,
+        }
+    }
+    //Segment index and the parameter inside that segment
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let segments = self.segments();
+        let scaled = t.clamp(0., 1.) * segments as f32;
+        let segment = (scaled as usize).min(segments - 1);
+        (segment, scaled - segment as f32)
+    }
+    fn combine(&self, segment: usize, weights: [f32; 4]) -> Vec3<f32> {
+        let first = segment * self.kind.step();
+        self.points[first..first + 4]
+            .iter()
+            .zip(weights.iter())
+            .fold(Vec3::new(0., 0., 0.), |sum, (&p, &w)| sum + p * w)
+    }
+    pub fn evaluate(&self, t: f32) -> Vec3<f32> {
+        let (segment, u) = self.locate(t);
+        self.combine(segment, self.kind.weights(u))
+    }
+    //Derivative with respect to t
+    pub fn tangent(&self, t: f32) -> Vec3<f32> {
+        let (segment, u) = self.locate(t);
+        self.combine(segment, self.kind.derivative_weights(u)) * self.segments() as f32
+    }
+    pub fn arc_length_table(&self, samples: usize) -> ArcLengthTable {
+        assert!(samples >= 1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut last = self.evaluate(0.);
+        let mut length = 0.;
+        lengths.push(0.);
+        for i in 1..=samples {
+            let point = self.evaluate(i as f32 / samples as f32);
+            length += (point - last).length();
+            lengths.push(length);
+            last = point;
+        }
+        ArcLengthTable { lengths }
+    }
+    //Line strip of evenly spaced parameters, positions only
+    pub fn tessellate(&self, subdivisions: usize) -> Vec<f32> {
+        assert!(subdivisions >= 1);
+        (0..=subdivisions)
+            .flat_map(|i| {
+                self.evaluate(i as f32 / subdivisions as f32)
+                    .to_array()
+                    .to_vec()
+            })
+            .collect()
+    }
+    //Line strip with points spaced evenly along the curve
+    pub fn tessellate_by_length(&self, table: &ArcLengthTable, subdivisions: usize) -> Vec<f32> {
+        assert!(subdivisions >= 1);
+        let total = table.total_length();
+        (0..=subdivisions)
+            .flat_map(|i| {
+                let t = table.parameter_at(total * i as f32 / subdivisions as f32);
+                self.evaluate(t).to_array().to_vec()
+            })
+            .collect()
+    }
+    //Layout of the buffers returned by tessellate, for VAOBuilder::from_vbo
+    pub fn line_strip_layout() -> DataLayout {
+        DataLayout::default().push(DataSpecification::float(3))
+    }
+}
+
+impl ArcLengthTable {
+    pub fn total_length(&self) -> f32 {
+        *self.lengths.last().unwrap()
+    }
+    //Spline parameter at which the curve has the given length, clamped to [0, 1]
+    pub fn parameter_at(&self, distance: f32) -> f32 {
+        let samples = (self.lengths.len() - 1) as f32;
+        if distance <= 0. {
+            return 0.;
+        }
+        if distance >= self.total_length() {
+            return 1.;
+        }
+        let upper = self.lengths.iter().position(|&l| l >= distance).unwrap();
+        let (l0, l1) = (self.lengths[upper - 1], self.lengths[upper]);
+        let fraction = if l1 > l0 {
+            (distance - l0) / (l1 - l0)
+        } else {
+            0.
+        };
+        (upper as f32 - 1. + fraction) / samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a - b).length() < 1e-4
+    }
+    fn points() -> Vec<Vec3<f32>> {
+        vec![
+            Vec3::new(0., 0., 0.),
+            Vec3::new(1., 2., 0.),
+            Vec3::new(3., 2., 1.),
+            Vec3::new(4., 0., 1.),
+            Vec3::new(5., -1., 0.),
+            Vec3::new(6., 1., -1.),
+            Vec3::new(8., 0., 0.),
+        ]
+    }
+
+    #[test]
+    pub fn test_spline_evaluation() {
+        let bezier = Spline::bezier(points());
+        assert_eq!(bezier.segments(), 2);
+        assert!(close(bezier.evaluate(0.), Vec3::new(0., 0., 0.)));
+        assert!(close(bezier.evaluate(0.5), Vec3::new(4., 0., 1.)));
+        assert!(close(bezier.evaluate(1.), Vec3::new(8., 0., 0.)));
+        assert!(close(bezier.evaluate(0.25), Vec3::new(2., 1.5, 0.5)));
+        //End tangents point at the neighbouring control points
+        assert!(close(bezier.tangent(0.), Vec3::new(6., 12., 0.)));
+
+        let catmull_rom = Spline::catmull_rom(points());
+        assert_eq!(catmull_rom.segments(), 4);
+        for (i, &p) in points()[1..6].iter().enumerate() {
+            assert!(close(catmull_rom.evaluate(i as f32 / 4.), p));
+        }
+        assert!(close(
+            catmull_rom.tangent(0.25),
+            (points()[3] - points()[1]) * 0.5 * 4.
+        ));
+
+        //Evenly spaced collinear control points give a straight, uniformly parameterized line
+        let line: Vec<Vec3<f32>> = (0..6).map(|i| Vec3::new(i as f32, 0., 0.)).collect();
+        let bspline = Spline::bspline(line);
+        assert_eq!(bspline.segments(), 3);
+        assert!(close(bspline.evaluate(0.), Vec3::new(1., 0., 0.)));
+        assert!(close(bspline.evaluate(0.5), Vec3::new(2.5, 0., 0.)));
+        assert!(close(bspline.tangent(0.7), Vec3::new(3., 0., 0.)));
+    }
+
+    #[test]
+    pub fn test_spline_tangents() {
+        for &kind in [
+            SplineKind::Bezier,
+            SplineKind::CatmullRom,
+            SplineKind::BSpline,
+        ]
+        .iter()
+        {
+            let spline = Spline::new(kind, points());
+            for &t in [0.1, 0.3, 0.6, 0.9].iter() {
+                let h = 1e-3;
+                let numeric = (spline.evaluate(t + h) - spline.evaluate(t - h)) * (0.5 / h);
+                assert!((numeric - spline.tangent(t)).length() < 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_arc_length() {
+        let line: Vec<Vec3<f32>> = [0., 1., 3., 4.]
+            .iter()
+            .map(|&x| Vec3::new(x, 0., 0.))
+            .collect();
+        let spline = Spline::bezier(line);
+        let table = spline.arc_length_table(256);
+        assert!((table.total_length() - 4.).abs() < 1e-4);
+        assert_eq!(table.parameter_at(-1.), 0.);
+        assert_eq!(table.parameter_at(5.), 1.);
+        let t = table.parameter_at(1.);
+        assert!((spline.evaluate(t).x - 1.).abs() < 1e-3);
+
+        let even = spline.tessellate_by_length(&table, 4);
+        assert_eq!(even.len(), 15);
+        for (i, vertex) in even.chunks(3).enumerate() {
+            assert!((vertex[0] - i as f32).abs() < 1e-3);
+        }
+        let strip = spline.tessellate(8);
+        assert_eq!(strip.len(), 27);
+        assert_eq!(&strip[24..], &[4., 0., 0.]);
+        let layout = Spline::line_strip_layout();
+        assert_eq!(layout.iter_attribute(&strip, 0).count(), 9);
+    }
+}
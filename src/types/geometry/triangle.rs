@@ -0,0 +1,67 @@
+use crate::types::geometry::plane::Plane;
+use crate::types::linalg::vector::Vec3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Triangle {
+    pub a: Vec3<f32>,
+    pub b: Vec3<f32>,
+    pub c: Vec3<f32>,
+}
+impl Triangle {
+    pub fn new(a: Vec3<f32>, b: Vec3<f32>, c: Vec3<f32>) -> Self {
+        Triangle { a, b, c }
+    }
+    //Unnormalized, its length is twice the area
+    pub fn normal(&self) -> Vec3<f32> {
+        (self.b - self.a).cross(self.c - self.a)
+    }
+    pub fn area(&self) -> f32 {
+        self.normal().length() * 0.5
+    }
+    pub fn centroid(&self) -> Vec3<f32> {
+        (self.a + self.b + self.c) * (1. / 3.)
+    }
+    pub fn plane(&self) -> Option<Plane> {
+        Plane::from_points(self.a, self.b, self.c)
+    }
+    //Weights of a, b and c for a point in the plane of the triangle
+    pub fn barycentric(&self, point: Vec3<f32>) -> Vec3<f32> {
+        let (e0, e1, d) = (self.b - self.a, self.c - self.a, point - self.a);
+        let (d00, d01, d11) = (e0.dot(e0), e0.dot(e1), e1.dot(e1));
+        let (d20, d21) = (d.dot(e0), d.dot(e1));
+        let denominator = d00 * d11 - d01 * d01;
+        let v = (d11 * d20 - d01 * d21) / denominator;
+        let w = (d00 * d21 - d01 * d20) / denominator;
+        Vec3::new(1. - v - w, v, w)
+    }
+    pub fn from_barycentric(&self, barycentric: Vec3<f32>) -> Vec3<f32> {
+        self.a * barycentric.x + self.b * barycentric.y + self.c * barycentric.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_triangle() {
+        let triangle = Triangle::new(
+            Vec3::new(0., 0., 0.),
+            Vec3::new(2., 0., 0.),
+            Vec3::new(0., 2., 0.),
+        );
+        assert_eq!(triangle.normal(), Vec3::new(0., 0., 4.));
+        assert_eq!(triangle.area(), 2.);
+        let plane = triangle.plane().unwrap();
+        assert_eq!(plane.signed_distance(Vec3::new(5., 5., 3.)), 3.);
+        assert_eq!(plane.project(Vec3::new(1., 1., 3.)), Vec3::new(1., 1., 0.));
+        let barycentric = triangle.barycentric(Vec3::new(0.5, 1., 0.));
+        assert_eq!(barycentric, Vec3::new(0.25, 0.25, 0.5));
+        assert_eq!(
+            triangle.from_barycentric(barycentric),
+            Vec3::new(0.5, 1., 0.)
+        );
+        let degenerate = Triangle::new(triangle.a, triangle.b, triangle.b * 2.);
+        assert!(degenerate.plane().is_none());
+    }
+}
@@ -0,0 +1,569 @@
+use crate::types::data::data_layout::DataLayout;
+use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::matrix::Matrix;
+use crate::types::linalg::vector::Vec3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Aabb {
+    pub min: Vec3<f32>,
+    pub max: Vec3<f32>,
+}
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BoundingSphere {
+    pub center: Vec3<f32>,
+    pub radius: f32,
+}
+//Box with orthonormal axes, half_extents are measured along each axis
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Obb {
+    pub center: Vec3<f32>,
+    pub axes: [Vec3<f32>; 3],
+    pub half_extents: Vec3<f32>,
+}
+
+//Reads a 2 or 3 component position attribute out of interleaved vertex data
+pub fn positions<'a>(
+    data: &'a [f32],
+    layout: &DataLayout,
+    attribute: usize,
+) -> impl Iterator<Item = Vec3<f32>> + 'a {
+    layout.iter_attribute(data, attribute).map(|p| {
+        debug_assert!(p.len() >= 2);
+        Vec3::new(p[0], p[1], if p.len() > 2 { p[2] } else { 0. })
+    })
+}
+fn component_min(a: Vec3<f32>, b: Vec3<f32>) -> Vec3<f32> {
+    Vec3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z))
+}
+fn component_max(a: Vec3<f32>, b: Vec3<f32>) -> Vec3<f32> {
+    Vec3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z))
+}
+
+impl Aabb {
+    pub fn new(min: Vec3<f32>, max: Vec3<f32>) -> Self {
+        debug_assert!(min.x <= max.x && min.y <= max.y && min.z <= max.z);
+        Aabb { min, max }
+    }
+    pub fn from_points<I: IntoIterator<Item = Vec3<f32>>>(points: I) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = iter.next()?;
+        Some(iter.fold(Aabb::new(first, first), |aabb, p| Aabb {
+            min: component_min(aabb.min, p),
+            max: component_max(aabb.max, p),
+        }))
+    }
+    pub fn from_vertices(data: &[f32], layout: &DataLayout, attribute: usize) -> Option<Self> {
+        Aabb::from_points(positions(data, layout, attribute))
+    }
+    pub fn center(&self) -> Vec3<f32> {
+        (self.min + self.max) * 0.5
+    }
+    pub fn half_extents(&self) -> Vec3<f32> {
+        (self.max - self.min) * 0.5
+    }
+    pub fn corners(&self) -> [Vec3<f32>; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            Vec3::new(a.x, a.y, a.z),
+            Vec3::new(b.x, a.y, a.z),
+            Vec3::new(a.x, b.y, a.z),
+            Vec3::new(b.x, b.y, a.z),
+            Vec3::new(a.x, a.y, b.z),
+            Vec3::new(b.x, a.y, b.z),
+            Vec3::new(a.x, b.y, b.z),
+            Vec3::new(b.x, b.y, b.z),
+        ]
+    }
+    //Box around the transformed corners, so it grows under rotation
+    pub fn transform(&self, matrix: &Matrix<f32>) -> Aabb {
+        Aabb::from_points(self.corners().iter().map(|&c| matrix.transform_point(c))).unwrap()
+    }
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: component_min(self.min, other.min),
+            max: component_max(self.max, other.max),
+        }
+    }
+    pub fn contains_point(&self, point: Vec3<f32>) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+            && self.min.z <= point.z
+            && point.z <= self.max.z
+    }
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+    pub fn closest_point(&self, point: Vec3<f32>) -> Vec3<f32> {
+        component_min(component_max(point, self.min), self.max)
+    }
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        sphere.intersects_aabb(self)
+    }
+}
+
+impl BoundingSphere {
+    pub fn new(center: Vec3<f32>, radius: f32) -> Self {
+        debug_assert!(radius >= 0.);
+        BoundingSphere { center, radius }
+    }
+    //Ritter's approximation, at most a few percent larger than the minimal sphere
+    pub fn from_points<I: IntoIterator<Item = Vec3<f32>>>(points: I) -> Option<Self> {
+        let points: Vec<Vec3<f32>> = points.into_iter().collect();
+        let first = *points.first()?;
+        let farthest = |from: Vec3<f32>| {
+            points.iter().copied().fold(from, |best, p| {
+                if (p - from).length_squared() > (best - from).length_squared() {
+                    p
+                } else {
+                    best
+                }
+            })
+        };
+        let a = farthest(first);
+        let b = farthest(a);
+        let mut sphere = BoundingSphere::new((a + b) * 0.5, (b - a).length() * 0.5);
+        for &p in points.iter() {
+            let distance = (p - sphere.center).length();
+            if distance > sphere.radius {
+                let radius = (sphere.radius + distance) * 0.5;
+                sphere.center =
+                    sphere.center + (p - sphere.center) * ((radius - sphere.radius) / distance);
+                sphere.radius = radius;
+            }
+        }
+        Some(sphere)
+    }
+    pub fn from_vertices(data: &[f32], layout: &DataLayout, attribute: usize) -> Option<Self> {
+        BoundingSphere::from_points(positions(data, layout, attribute))
+    }
+    //The radius is scaled by the largest axis scale of the transform
+    pub fn transform(&self, matrix: &Matrix<f32>) -> BoundingSphere {
+        let scale = [
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 1., 0.),
+            Vec3::new(0., 0., 1.),
+        ]
+        .iter()
+        .map(|&axis| matrix.transform_vector(axis).length())
+        .fold(0f32, f32::max);
+        BoundingSphere::new(matrix.transform_point(self.center), self.radius * scale)
+    }
+    pub fn merge(&self, other: &BoundingSphere) -> BoundingSphere {
+        let offset = other.center - self.center;
+        let distance = offset.length();
+        if distance + other.radius <= self.radius {
+            return *self;
+        }
+        if distance + self.radius <= other.radius {
+            return *other;
+        }
+        let radius = (distance + self.radius + other.radius) * 0.5;
+        let center = self.center + offset * ((radius - self.radius) / distance);
+        BoundingSphere::new(center, radius)
+    }
+    pub fn contains_point(&self, point: Vec3<f32>) -> bool {
+        (point - self.center).length_squared() <= self.radius * self.radius
+    }
+    pub fn contains(&self, other: &BoundingSphere) -> bool {
+        (other.center - self.center).length() + other.radius <= self.radius
+    }
+    pub fn intersects(&self, other: &BoundingSphere) -> bool {
+        let radius = self.radius + other.radius;
+        (other.center - self.center).length_squared() <= radius * radius
+    }
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.contains_point(aabb.closest_point(self.center))
+    }
+}
+
+impl Obb {
+    pub fn new(center: Vec3<f32>, axes: [Vec3<f32>; 3], half_extents: Vec3<f32>) -> Self {
+        Obb {
+            center,
+            axes,
+            half_extents,
+        }
+    }
+    //Fits the box to the principal axes of the point cloud
+    pub fn from_points<I: IntoIterator<Item = Vec3<f32>>>(points: I) -> Option<Self> {
+        let points: Vec<Vec3<f32>> = points.into_iter().collect();
+        if points.is_empty() {
+            return None;
+        }
+        let n = points.len() as f32;
+        let mean = points.iter().fold(Vec3::new(0., 0., 0.), |sum, &p| sum + p) * (1. / n);
+        let covariance = Matrix::from_closure(
+            |index| {
+                let (r, c) = (index / 3, index % 3);
+                points
+                    .iter()
+                    .map(|&p| {
+                        let d = (p - mean).to_array();
+                        d[r] * d[c]
+                    })
+                    .sum::<f32>()
+                    / n
+            },
+            Dimension::new(3, 3),
+        );
+        let axes = match covariance.symmetric_eigen() {
+            Ok(eigen) => {
+                let v = eigen.vectors;
+                let axis = |c: usize| Vec3::new(v[(0, c)], v[(1, c)], v[(2, c)]);
+                [axis(0), axis(1), axis(2)]
+            }
+            Err(_) => Obb::identity_axes(),
+        };
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for &p in points.iter() {
+            for i in 0..3 {
+                let projection = (p - mean).dot(axes[i]);
+                min[i] = min[i].min(projection);
+                max[i] = max[i].max(projection);
+            }
+        }
+        let mut center = mean;
+        for i in 0..3 {
+            center = center + axes[i] * ((min[i] + max[i]) * 0.5);
+        }
+        let half_extents = Vec3::new(max[0] - min[0], max[1] - min[1], max[2] - min[2]) * 0.5;
+        Some(Obb::new(center, axes, half_extents))
+    }
+    pub fn from_vertices(data: &[f32], layout: &DataLayout, attribute: usize) -> Option<Self> {
+        Obb::from_points(positions(data, layout, attribute))
+    }
+    fn identity_axes() -> [Vec3<f32>; 3] {
+        [
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., 1., 0.),
+            Vec3::new(0., 0., 1.),
+        ]
+    }
+    fn half_extent(&self, i: usize) -> f32 {
+        self.half_extents.to_array()[i]
+    }
+    pub fn corners(&self) -> [Vec3<f32>; 8] {
+        let mut corners = [self.center; 8];
+        for (index, corner) in corners.iter_mut().enumerate() {
+            for i in 0..3 {
+                let sign = if index & (1 << i) == 0 { -1. } else { 1. };
+                *corner = *corner + self.axes[i] * (sign * self.half_extent(i));
+            }
+        }
+        corners
+    }
+    pub fn to_aabb(&self) -> Aabb {
+        Aabb::from_points(self.corners().iter().copied()).unwrap()
+    }
+    //Bounds the transformed box. Exact for rotations, translations and scales along the box axes,
+    //other scales and shears turn the box into a parallelepiped, whose corners are then fitted
+    //with the transformed axes made orthonormal again, longest first.
+    //Axes collapsed by a zero scale get a half extent of 0 and an orthogonal replacement direction.
+    pub fn transform(&self, matrix: &Matrix<f32>) -> Obb {
+        let transformed = [
+            matrix.transform_vector(self.axes[0]),
+            matrix.transform_vector(self.axes[1]),
+            matrix.transform_vector(self.axes[2]),
+        ];
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| {
+            transformed[b]
+                .length()
+                .partial_cmp(&transformed[a].length())
+                .unwrap()
+        });
+        let largest = transformed[order[0]].length();
+        //Gram-Schmidt
+        let mut valid = [false; 3];
+        let mut axes = self.axes;
+        for (k, &i) in order.iter().enumerate() {
+            let mut axis = transformed[i];
+            for &j in order[..k].iter().filter(|&&j| valid[j]) {
+                axis = axis - axes[j] * axis.dot(axes[j]);
+            }
+            let length = axis.length();
+            if largest > 0. && length > largest * 1e-6 {
+                valid[i] = true;
+                axes[i] = axis * (1. / length);
+            }
+        }
+        if valid.iter().any(|&v| v) {
+            for &i in order.iter() {
+                if valid[i] {
+                    continue;
+                }
+                let others: Vec<Vec3<f32>> =
+                    (0..3).filter(|&j| valid[j]).map(|j| axes[j]).collect();
+                axes[i] = if others.len() == 2 {
+                    others[0].cross(others[1]).normalize()
+                } else {
+                    perpendicular(others[0])
+                };
+                valid[i] = true;
+            }
+        }
+        debug_assert!(axes
+            .iter()
+            .all(|a| a.x.is_finite() && a.y.is_finite() && a.z.is_finite()));
+        let corners = self.corners();
+        let mut center = Vec3::new(0., 0., 0.);
+        let mut half_extents = [0.; 3];
+        for i in 0..3 {
+            let mut min = f32::INFINITY;
+            let mut max = f32::NEG_INFINITY;
+            for &corner in corners.iter() {
+                let projection = matrix.transform_point(corner).dot(axes[i]);
+                min = min.min(projection);
+                max = max.max(projection);
+            }
+            center = center + axes[i] * ((min + max) * 0.5);
+            half_extents[i] = (max - min) * 0.5;
+        }
+        Obb::new(
+            center,
+            axes,
+            Vec3::new(half_extents[0], half_extents[1], half_extents[2]),
+        )
+    }
+    pub fn merge(&self, other: &Obb) -> Obb {
+        Obb::from_points(self.corners().iter().chain(other.corners().iter()).copied()).unwrap()
+    }
+    pub fn contains_point(&self, point: Vec3<f32>) -> bool {
+        let d = point - self.center;
+        (0..3).all(|i| d.dot(self.axes[i]).abs() <= self.half_extent(i) * (1. + 1e-5) + 1e-6)
+    }
+    pub fn contains(&self, other: &Obb) -> bool {
+        other.corners().iter().all(|&c| self.contains_point(c))
+    }
+    //Half length of the box projected onto an axis
+    fn projected_radius(&self, axis: Vec3<f32>) -> f32 {
+        (0..3)
+            .map(|i| self.half_extent(i) * self.axes[i].dot(axis).abs())
+            .sum()
+    }
+    //Separating axis test over the 3 + 3 face normals and 9 edge cross products
+    pub fn intersects(&self, other: &Obb) -> bool {
+        let d = other.center - self.center;
+        let mut candidates = Vec::with_capacity(15);
+        candidates.extend_from_slice(&self.axes);
+        candidates.extend_from_slice(&other.axes);
+        for a in self.axes.iter() {
+            for b in other.axes.iter() {
+                candidates.push(a.cross(*b));
+            }
+        }
+        !candidates.iter().any(|&axis| {
+            axis.length_squared() > 1e-10
+                && d.dot(axis).abs() > self.projected_radius(axis) + other.projected_radius(axis)
+        })
+    }
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.intersects(&Obb::from(*aabb))
+    }
+}
+impl From<Aabb> for Obb {
+    fn from(aabb: Aabb) -> Self {
+        Obb::new(aabb.center(), Obb::identity_axes(), aabb.half_extents())
+    }
+}
+
+//Some unit vector orthogonal to the unit vector v
+fn perpendicular(v: Vec3<f32>) -> Vec3<f32> {
+    let helper = if v.x.abs() < 0.577 {
+        Vec3::new(1., 0., 0.)
+    } else {
+        Vec3::new(0., 1., 0.)
+    };
+    v.cross(helper).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a - b).length() < 1e-4
+    }
+    fn cube_vertices() -> (Vec<f32>, DataLayout) {
+        //Position followed by a color
+        let mut data = Vec::new();
+        for i in 0..8 {
+            let x = if i & 1 == 0 { -1. } else { 1. };
+            let y = if i & 2 == 0 { -2. } else { 2. };
+            let z = if i & 4 == 0 { 0. } else { 3. };
+            data.extend_from_slice(&[x, y, z, 0.5, 0.5, 0.5]);
+        }
+        let layout = DataLayout::infer_from_f32slice(&data, &[3], gl::FALSE, 8);
+        (data, layout)
+    }
+
+    #[test]
+    pub fn test_aabb() {
+        let (data, layout) = cube_vertices();
+        let aabb = Aabb::from_vertices(&data, &layout, 0).unwrap();
+        assert_eq!(aabb.min, Vec3::new(-1., -2., 0.));
+        assert_eq!(aabb.max, Vec3::new(1., 2., 3.));
+        assert!(Aabb::from_vertices(&[], &layout, 0).is_none());
+        let moved = aabb.transform(&Matrix::identity4().translate4(10., 0., 0.));
+        assert!(close(moved.min, Vec3::new(9., -2., 0.)));
+        assert!(!aabb.intersects(&moved));
+        let rotated = aabb.transform(&Matrix::identity4().rot90(0., 0., 1.));
+        assert!(close(rotated.min, Vec3::new(-2., -1., 0.)));
+        assert!(close(rotated.max, Vec3::new(2., 1., 3.)));
+        let merged = aabb.merge(&moved);
+        assert!(merged.contains(&aabb) && merged.contains(&moved));
+        assert!(!aabb.contains(&merged));
+        assert!(aabb.intersects(&rotated));
+        assert!(aabb.contains_point(Vec3::new(0., 0., 1.)));
+        assert!(!aabb.contains_point(Vec3::new(0., 0., -1.)));
+        assert!(aabb.intersects_sphere(&BoundingSphere::new(Vec3::new(2., 0., 1.), 1.)));
+        assert!(!aabb.intersects_sphere(&BoundingSphere::new(Vec3::new(2., 3., 1.), 1.)));
+    }
+
+    #[test]
+    pub fn test_bounding_sphere() {
+        let (data, layout) = cube_vertices();
+        let sphere = BoundingSphere::from_vertices(&data, &layout, 0).unwrap();
+        assert!(close(sphere.center, Vec3::new(0., 0., 1.5)));
+        for p in positions(&data, &layout, 0) {
+            assert!((p - sphere.center).length() <= sphere.radius + 1e-5);
+        }
+        let scaled = sphere.transform(
+            &Matrix::identity4()
+                .translate4(1., 1., 1.)
+                .scale4(1., 3., 2.),
+        );
+        assert!(close(scaled.center, Vec3::new(1., 1., 4.)));
+        assert!((scaled.radius - sphere.radius * 3.).abs() < 1e-5);
+        let a = BoundingSphere::new(Vec3::new(0., 0., 0.), 1.);
+        let b = BoundingSphere::new(Vec3::new(4., 0., 0.), 1.);
+        assert!(!a.intersects(&b));
+        let merged = a.merge(&b);
+        assert!(close(merged.center, Vec3::new(2., 0., 0.)));
+        assert_eq!(merged.radius, 3.);
+        assert!(merged.contains(&a) && merged.contains(&b));
+        assert_eq!(merged.merge(&a), merged);
+        assert!(a.contains_point(Vec3::new(0.5, 0.5, 0.5)));
+        assert!(!a.contains_point(Vec3::new(1., 1., 0.)));
+    }
+
+    #[test]
+    pub fn test_obb() {
+        let (data, layout) = cube_vertices();
+        let rotation = Matrix::identity4()
+            .translate4(5., 0., 0.)
+            .rotate4(0.6, 0., 0.8, 0.7);
+        let points: Vec<Vec3<f32>> = positions(&data, &layout, 0)
+            .map(|p| rotation.transform_point(p))
+            .collect();
+        let obb = Obb::from_points(points.iter().copied()).unwrap();
+        let mut half = obb.half_extents.to_array();
+        half.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((half[0] - 1.).abs() < 1e-3);
+        assert!((half[1] - 1.5).abs() < 1e-3);
+        assert!((half[2] - 2.).abs() < 1e-3);
+        assert!(points.iter().all(|&p| obb.contains_point(p)));
+        assert!(close(
+            obb.center,
+            rotation.transform_point(Vec3::new(0., 0., 1.5))
+        ));
+
+        let unit = Obb::from(Aabb::new(Vec3::new(-1., -1., -1.), Vec3::new(1., 1., 1.)));
+        let turned =
+            unit.transform(&Matrix::identity4().rotate4(0., 0., 1., std::f32::consts::FRAC_PI_4));
+        assert!(close(turned.half_extents, unit.half_extents));
+        //The rotated corner reaches sqrt(2) along x
+        assert!(turned.intersects(&unit.transform(&Matrix::identity4().translate4(2.3, 0., 0.))));
+        assert!(!turned.intersects(&unit.transform(&Matrix::identity4().translate4(2.5, 0., 0.))));
+        assert!(turned.intersects_aabb(&Aabb::new(
+            Vec3::new(1.3, -0.1, -0.1),
+            Vec3::new(2., 0.1, 0.1)
+        )));
+        assert!(!turned.intersects_aabb(&Aabb::new(
+            Vec3::new(1.3, 1.3, -0.1),
+            Vec3::new(2., 2., 0.1)
+        )));
+        let far = unit.transform(&Matrix::identity4().translate4(0., 10., 0.));
+        let merged = unit.merge(&far);
+        assert!(merged.contains(&unit) && merged.contains(&far));
+        assert!((merged.to_aabb().max.y - 11.).abs() < 1e-4);
+    }
+
+    #[test]
+    pub fn test_obb_degenerate_transform() {
+        let unit = Obb::new(
+            Vec3::new(0., 0., 0.),
+            [
+                Vec3::new(1., 0., 0.),
+                Vec3::new(0., 1., 0.),
+                Vec3::new(0., 0., 1.),
+            ],
+            Vec3::new(1., 1., 1.),
+        );
+        let check = |obb: &Obb| {
+            for axis in obb.axes.iter() {
+                assert!((axis.length() - 1.).abs() < 1e-5);
+            }
+            for i in 0..3 {
+                for j in i + 1..3 {
+                    assert!(obb.axes[i].dot(obb.axes[j]).abs() < 1e-5);
+                }
+            }
+        };
+        //Flattened into the xz plane
+        let flat = unit.transform(
+            &Matrix::identity4()
+                .scale4(1., 0., 1.)
+                .rotate4(0., 1., 0., 0.5),
+        );
+        check(&flat);
+        assert!(flat.contains_point(Vec3::new(0.5, 0., 0.5)));
+        assert!(!flat.contains_point(Vec3::new(0., 0.1, 0.)));
+        //Collapsed onto the x axis, then into a single point
+        let line = unit.transform(&Matrix::identity4().scale4(2., 0., 0.));
+        check(&line);
+        assert!(line.contains_point(Vec3::new(1.5, 0., 0.)));
+        assert!(!line.contains_point(Vec3::new(1.5, 0.1, 0.)));
+        let point = unit.transform(
+            &Matrix::identity4()
+                .translate4(1., 2., 3.)
+                .scale4(0., 0., 0.),
+        );
+        check(&point);
+        assert!(point.contains_point(Vec3::new(1., 2., 3.)));
+    }
+
+    #[test]
+    pub fn test_obb_non_uniform_transform() {
+        let unit = Obb::from(Aabb::new(Vec3::new(-1., -1., -1.), Vec3::new(1., 1., 1.)));
+        let matrix =
+            Matrix::identity4()
+                .scale4(2., 1., 1.)
+                .rotate4(0., 0., 1., std::f32::consts::FRAC_PI_4);
+        let obb = unit.transform(&matrix);
+        for i in 0..3 {
+            assert!((obb.axes[i].length() - 1.).abs() < 1e-5);
+            for j in i + 1..3 {
+                assert!(obb.axes[i].dot(obb.axes[j]).abs() < 1e-5);
+            }
+        }
+        for &corner in unit.corners().iter() {
+            assert!(obb.contains_point(matrix.transform_point(corner)));
+        }
+        //The rotated square stretched along x reaches 2 * sqrt(2) on x but only sqrt(2) on y
+        assert!(obb.contains_point(Vec3::new(2.8, 0., 0.)));
+        assert!(!obb.contains_point(Vec3::new(0., 1.5, 0.)));
+        assert!(!obb.contains_point(Vec3::new(0., 0., 1.1)));
+    }
+}
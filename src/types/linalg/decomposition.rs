@@ -0,0 +1,383 @@
+use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::matrix::{Matrix, MatrixError, Real};
+
+//P * A = L * U, L has an implicit unit diagonal and is stored together with U
+pub struct LU<T> {
+    pub lu: Matrix<T>,
+    pub permutation: Vec<usize>,
+    sign: T,
+}
+//A = Q * R with Q having orthonormal columns (thin decomposition)
+pub struct QR<T> {
+    pub q: Matrix<T>,
+    pub r: Matrix<T>,
+}
+//A = L * L^T, only the lower triangle of A is read
+pub struct Cholesky<T> {
+    pub l: Matrix<T>,
+}
+//A = V * diag(values) * V^T, eigenvalues sorted in descending order
+pub struct SymmetricEigen<T> {
+    pub values: Vec<T>,
+    pub vectors: Matrix<T>,
+}
+
+fn check_square(dimension: Dimension) -> Result<usize, MatrixError> {
+    if dimension.rows == dimension.columns {
+        Ok(dimension.rows)
+    } else {
+        Err(MatrixError::NotSquare(dimension))
+    }
+}
+fn check_rhs(dimension: Dimension, rows: usize) -> Result<(), MatrixError> {
+    if dimension.rows == rows {
+        Ok(())
+    } else {
+        Err(MatrixError::DimensionMismatch {
+            left: Dimension::new(rows, rows),
+            right: dimension,
+        })
+    }
+}
+
+impl<T: Real> Matrix<T> {
+    fn tolerance(&self) -> T {
+        let max = self.data.iter().fold(
+            T::zero(),
+            |curr, &t| if t.abs() > curr { t.abs() } else { curr },
+        );
+        max * T::from_usize(self.dimension.rows.max(self.dimension.columns)) * T::epsilon()
+    }
+    pub fn lu(&self) -> Result<LU<T>, MatrixError> {
+        let n = check_square(self.dimension)?;
+        let tolerance = self.tolerance();
+        let mut lu = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+        for k in 0..n {
+            let mut pivot = k;
+            for i in k + 1..n {
+                if lu[(i, k)].abs() > lu[(pivot, k)].abs() {
+                    pivot = i;
+                }
+            }
+            if lu[(pivot, k)].abs() <= tolerance {
+                return Err(MatrixError::Singular);
+            }
+            if pivot != k {
+                for j in 0..n {
+                    lu.data.swap(pivot * n + j, k * n + j);
+                }
+                permutation.swap(pivot, k);
+                sign = -sign;
+            }
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / lu[(k, k)];
+                lu[(i, k)] = factor;
+                for j in k + 1..n {
+                    lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                }
+            }
+        }
+        Ok(LU {
+            lu,
+            permutation,
+            sign,
+        })
+    }
+    pub fn qr(&self) -> Result<QR<T>, MatrixError> {
+        let (m, n) = (self.dimension.rows, self.dimension.columns);
+        if m < n {
+            return Err(MatrixError::Underdetermined(self.dimension));
+        }
+        //Householder reflections H = I - 2vv^T applied to R from the left and accumulated into Q
+        let two = T::one() + T::one();
+        let mut r = self.clone();
+        let mut q = Matrix::<T>::identity(m);
+        let mut v = vec![T::zero(); m];
+        for k in 0..n.min(m - 1) {
+            let norm = (k..m)
+                .fold(T::zero(), |curr, i| curr + r[(i, k)] * r[(i, k)])
+                .sqrt();
+            if norm == T::zero() {
+                continue;
+            }
+            let alpha = if r[(k, k)] > T::zero() { -norm } else { norm };
+            for i in k..m {
+                v[i] = r[(i, k)];
+            }
+            v[k] = v[k] - alpha;
+            let v_norm = (k..m).fold(T::zero(), |curr, i| curr + v[i] * v[i]).sqrt();
+            if v_norm == T::zero() {
+                continue;
+            }
+            for x in v.iter_mut().skip(k) {
+                *x = *x / v_norm;
+            }
+            for j in 0..n {
+                let s = (k..m).fold(T::zero(), |curr, i| curr + v[i] * r[(i, j)]);
+                for i in k..m {
+                    r[(i, j)] = r[(i, j)] - two * v[i] * s;
+                }
+            }
+            for i in 0..m {
+                let s = (k..m).fold(T::zero(), |curr, l| curr + q[(i, l)] * v[l]);
+                for l in k..m {
+                    q[(i, l)] = q[(i, l)] - two * s * v[l];
+                }
+            }
+        }
+        Ok(QR {
+            q: Matrix::from_closure(
+                |index| q.data[(index / n) * m + index % n],
+                Dimension::new(m, n),
+            ),
+            r: Matrix::from_closure(|index| r.data[index], Dimension::new(n, n)),
+        })
+    }
+    pub fn cholesky(&self) -> Result<Cholesky<T>, MatrixError> {
+        let n = check_square(self.dimension)?;
+        let mut l = Matrix::zero(self.dimension);
+        for j in 0..n {
+            let d = (0..j).fold(self[(j, j)], |curr, k| curr - l[(j, k)] * l[(j, k)]);
+            if d <= T::zero() {
+                return Err(MatrixError::NotPositiveDefinite);
+            }
+            l[(j, j)] = d.sqrt();
+            for i in j + 1..n {
+                let s = (0..j).fold(self[(i, j)], |curr, k| curr - l[(i, k)] * l[(j, k)]);
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+        Ok(Cholesky { l })
+    }
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<T>, MatrixError> {
+        let n = check_square(self.dimension)?;
+        //Cyclic Jacobi rotations until the off-diagonal part vanishes
+        let mut a = self.clone();
+        let mut vectors = Matrix::<T>::identity(n);
+        let tolerance = self.tolerance() * self.tolerance();
+        for _ in 0..100 {
+            let mut off = T::zero();
+            for p in 0..n {
+                for q in p + 1..n {
+                    off = off + a[(p, q)] * a[(p, q)];
+                }
+            }
+            if off <= tolerance {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[(p, q)] == T::zero() {
+                        continue;
+                    }
+                    let theta = (a[(q, q)] - a[(p, p)]) / ((T::one() + T::one()) * a[(p, q)]);
+                    let t = T::one() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                    let t = if theta < T::zero() { -t } else { t };
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = c * akp - s * akq;
+                        a[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = c * apk - s * aqk;
+                        a[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (vectors[(k, p)], vectors[(k, q)]);
+                        vectors[(k, p)] = c * vkp - s * vkq;
+                        vectors[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[(j, j)].partial_cmp(&a[(i, i)]).unwrap());
+        Ok(SymmetricEigen {
+            values: order.iter().map(|&i| a[(i, i)]).collect(),
+            vectors: Matrix::from_closure(
+                |index| vectors[(index / n, order[index % n])],
+                self.dimension,
+            ),
+        })
+    }
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.lu()?.solve(b)
+    }
+    pub fn least_squares(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.qr()?.least_squares(b)
+    }
+}
+
+//Solves U * x = y in place for an upper triangular U, reading only the upper triangle
+fn back_substitution<T: Real>(u: &Matrix<T>, x: &mut Matrix<T>) {
+    let n = u.dimension.rows;
+    for c in 0..x.dimension.columns {
+        for i in (0..n).rev() {
+            let s = (i + 1..n).fold(x[(i, c)], |curr, j| curr - u[(i, j)] * x[(j, c)]);
+            x[(i, c)] = s / u[(i, i)];
+        }
+    }
+}
+
+impl<T: Real> LU<T> {
+    pub fn determinant(&self) -> T {
+        let n = self.lu.dimension.rows;
+        (0..n).fold(self.sign, |curr, i| curr * self.lu[(i, i)])
+    }
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let n = self.lu.dimension.rows;
+        check_rhs(b.dimension, n)?;
+        let mut x = Matrix::from_closure(
+            |index| {
+                b[(
+                    self.permutation[index / b.dimension.columns],
+                    index % b.dimension.columns,
+                )]
+            },
+            b.dimension,
+        );
+        for c in 0..x.dimension.columns {
+            for i in 0..n {
+                let s = (0..i).fold(x[(i, c)], |curr, j| curr - self.lu[(i, j)] * x[(j, c)]);
+                x[(i, c)] = s;
+            }
+        }
+        back_substitution(&self.lu, &mut x);
+        Ok(x)
+    }
+    pub fn inverse(&self) -> Matrix<T> {
+        self.solve(&Matrix::identity(self.lu.dimension.rows))
+            .unwrap()
+    }
+}
+impl<T: Real> QR<T> {
+    pub fn least_squares(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        check_rhs(b.dimension, self.q.dimension.rows)?;
+        let n = self.r.dimension.rows;
+        let tolerance = self.r.tolerance();
+        if (0..n).any(|i| self.r[(i, i)].abs() <= tolerance) {
+            return Err(MatrixError::Singular);
+        }
+        let mut x = self.q.transpose().checked_mul(b)?;
+        back_substitution(&self.r, &mut x);
+        Ok(x)
+    }
+}
+impl<T: Real> Cholesky<T> {
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let n = self.l.dimension.rows;
+        check_rhs(b.dimension, n)?;
+        let mut x = b.clone();
+        for c in 0..x.dimension.columns {
+            for i in 0..n {
+                let s = (0..i).fold(x[(i, c)], |curr, j| curr - self.l[(i, j)] * x[(j, c)]);
+                x[(i, c)] = s / self.l[(i, i)];
+            }
+        }
+        back_substitution(&self.l.transpose(), &mut x);
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_eq(m: &Matrix<f64>, data: &[f64]) -> bool {
+        m.data.len() == data.len()
+            && m.data
+                .iter()
+                .zip(data.iter())
+                .all(|(a, b)| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    pub fn test_lu() {
+        let a = Matrix::from_data(vec![2., 1., 1., 3.], Dimension::new(2, 2));
+        let x = a
+            .solve(&Matrix::from_data(vec![3., 5.], Dimension::new(2, 1)))
+            .unwrap();
+        assert!(approx_eq(&x, &[0.8, 1.4]));
+        let a = Matrix::from_data(
+            vec![1., 2., 3., 0., 1., 4., 5., 6., 0.],
+            Dimension::new(3, 3),
+        );
+        let lu = a.lu().unwrap();
+        assert!((lu.determinant() - 1.).abs() < 1e-9);
+        assert!(approx_eq(
+            &lu.inverse(),
+            &[-24., 18., 5., 20., -15., -4., -5., 4., 1.]
+        ));
+        let singular = Matrix::from_data(vec![1., 2., 2., 4.], Dimension::new(2, 2));
+        assert_eq!(singular.lu().err(), Some(MatrixError::Singular));
+        let a32 = Matrix::from_data(vec![0f32, 1., 1., 0.], Dimension::new(2, 2));
+        let x = a32
+            .solve(&Matrix::from_data(vec![2., 3.], Dimension::new(2, 1)))
+            .unwrap();
+        assert!(x.data == vec![3., 2.]);
+    }
+
+    #[test]
+    pub fn test_qr() {
+        //Fit y = c0 + c1 * x through (0, 0), (1, 1), (2, 1)
+        let a = Matrix::from_data(vec![1., 0., 1., 1., 1., 2.], Dimension::new(3, 2));
+        let b = Matrix::from_data(vec![0., 1., 1.], Dimension::new(3, 1));
+        let x = a.least_squares(&b).unwrap();
+        assert!(approx_eq(&x, &[1. / 6., 0.5]));
+        let qr = a.qr().unwrap();
+        assert!(approx_eq(&(&qr.q * &qr.r), &a.data));
+        assert!(approx_eq(&(&qr.q.transpose() * &qr.q), &[1., 0., 0., 1.]));
+        assert!(qr.r[(1, 0)].abs() < 1e-12);
+        let wide = a.transpose();
+        assert!(wide.qr().is_err());
+    }
+
+    #[test]
+    pub fn test_cholesky() {
+        let a = Matrix::from_data(
+            vec![4., 12., -16., 12., 37., -43., -16., -43., 98.],
+            Dimension::new(3, 3),
+        );
+        let cholesky = a.cholesky().unwrap();
+        assert!(approx_eq(
+            &cholesky.l,
+            &[2., 0., 0., 6., 1., 0., -8., 5., 3.]
+        ));
+        let x = cholesky
+            .solve(&Matrix::from_data(
+                vec![4., 12., -16.],
+                Dimension::new(3, 1),
+            ))
+            .unwrap();
+        assert!(approx_eq(&x, &[1., 0., 0.]));
+        let indefinite = Matrix::from_data(vec![1., 2., 2., 1.], Dimension::new(2, 2));
+        assert_eq!(
+            indefinite.cholesky().err(),
+            Some(MatrixError::NotPositiveDefinite)
+        );
+    }
+
+    #[test]
+    pub fn test_symmetric_eigen() {
+        let a = Matrix::from_data(vec![2., 1., 1., 2.], Dimension::new(2, 2));
+        let eigen = a.symmetric_eigen().unwrap();
+        assert!((eigen.values[0] - 3.).abs() < 1e-9 && (eigen.values[1] - 1.).abs() < 1e-9);
+        let v = eigen.vectors.column(0).to_vec();
+        assert!((v[0].abs() - 0.5f64.sqrt()).abs() < 1e-9 && (v[0] - v[1]).abs() < 1e-9);
+        let a = Matrix::from_data(
+            vec![4., 1., 2., 1., 3., 0., 2., 0., 5.],
+            Dimension::new(3, 3),
+        );
+        let eigen = a.symmetric_eigen().unwrap();
+        for (i, &value) in eigen.values.iter().enumerate() {
+            let v = Matrix::from_data(eigen.vectors.column(i).to_vec(), Dimension::new(3, 1));
+            assert!(approx_eq(&(&a * &v), &(&v * value).data));
+        }
+        assert!((eigen.values.iter().sum::<f64>() - 12.).abs() < 1e-9);
+    }
+}
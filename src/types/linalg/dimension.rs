@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Dimension {
     pub rows: usize,
     pub columns: usize,
@@ -0,0 +1,180 @@
+use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::matrix::{Matrix, Zero};
+use crate::types::linalg::vector::{Vec3, Vec4};
+use std::ops::{Add, Mul};
+
+//Row-major, like Matrix<T>
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Mat3<T> {
+    pub data: [T; 9],
+}
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Mat4<T> {
+    pub data: [T; 16],
+}
+
+macro_rules! impl_fixed_matrix {
+    ($mat: ident, $vec: ident, $n: expr, $($field: ident),+) => {
+        impl<T: Copy> $mat<T> {
+            pub const fn from_data(data: [T; $n * $n]) -> Self {
+                $mat { data }
+            }
+            pub fn as_ptr(&self) -> *const T {
+                self.data.as_ptr()
+            }
+            #[inline(always)]
+            pub fn get(&self, row: usize, column: usize) -> T {
+                self.data[row * $n + column]
+            }
+            pub fn transpose(&self) -> $mat<T> {
+                let mut data = self.data;
+                for row in 0..$n {
+                    for column in 0..$n {
+                        data[row * $n + column] = self.data[column * $n + row];
+                    }
+                }
+                $mat { data }
+            }
+        }
+        impl<T: Zero + Copy> $mat<T> {
+            pub fn zero() -> Self {
+                $mat { data: [T::zero(); $n * $n] }
+            }
+        }
+        impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy> Mul for $mat<T> {
+            type Output = $mat<T>;
+            fn mul(self, other: $mat<T>) -> Self::Output {
+                let mut data = [T::zero(); $n * $n];
+                for row in 0..$n {
+                    for column in 0..$n {
+                        data[row * $n + column] = (0..$n).fold(T::zero(), |curr, i| {
+                            curr + self.data[row * $n + i] * other.data[i * $n + column]
+                        });
+                    }
+                }
+                $mat { data }
+            }
+        }
+        impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy> Mul<$vec<T>> for $mat<T> {
+            type Output = $vec<T>;
+            fn mul(self, other: $vec<T>) -> Self::Output {
+                let other = other.to_array();
+                let mut rows = (0..$n).map(|row| {
+                    (0..$n).fold(T::zero(), |curr, i| curr + self.data[row * $n + i] * other[i])
+                });
+                $vec { $($field: rows.next().unwrap()),+ }
+            }
+        }
+        impl<T: Mul<T, Output = T> + Copy> Mul<T> for $mat<T> {
+            type Output = $mat<T>;
+            fn mul(mut self, other: T) -> Self::Output {
+                for t in self.data.iter_mut() {
+                    *t = *t * other;
+                }
+                self
+            }
+        }
+        //Conversion from and to the heap allocated Matrix
+        impl<T: Copy> From<$mat<T>> for Matrix<T> {
+            fn from(mat: $mat<T>) -> Self {
+                Matrix::from_data(mat.data.to_vec(), Dimension::new($n, $n))
+            }
+        }
+        impl<T: Copy> From<&Matrix<T>> for $mat<T> {
+            fn from(matrix: &Matrix<T>) -> Self {
+                debug_assert!(matrix.dimension == Dimension::new($n, $n));
+                let mut data = [matrix.data[0]; $n * $n];
+                data.copy_from_slice(&matrix.data);
+                $mat { data }
+            }
+        }
+    };
+}
+impl_fixed_matrix!(Mat3, Vec3, 3, x, y, z);
+impl_fixed_matrix!(Mat4, Vec4, 4, x, y, z, w);
+
+impl Mat3<f32> {
+    pub const fn identity() -> Self {
+        Mat3::from_data([1., 0., 0., 0., 1., 0., 0., 0., 1.])
+    }
+}
+impl Mat4<f32> {
+    pub const fn identity() -> Self {
+        Mat4::from_data([
+            1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
+        ])
+    }
+    pub fn scale(self, s1: f32, s2: f32, s3: f32) -> Mat4<f32> {
+        let mut res = Mat4::identity();
+        res.data[0] = s1;
+        res.data[5] = s2;
+        res.data[10] = s3;
+        self * res
+    }
+    pub fn sscale(self, s: f32) -> Mat4<f32> {
+        self.scale(s, s, s)
+    }
+    pub fn translate(self, t1: f32, t2: f32, t3: f32) -> Mat4<f32> {
+        let mut res = Mat4::identity();
+        res.data[3] = t1;
+        res.data[7] = t2;
+        res.data[11] = t3;
+        self * res
+    }
+    pub fn rotate(self, rx: f32, ry: f32, rz: f32, theta: f32) -> Mat4<f32> {
+        let cos = theta.cos();
+        let sin = theta.sin();
+        let res = Mat4::from_data([
+            cos + rx * rx * (1.0 - cos),
+            rx * ry * (1.0 - cos) - rz * sin,
+            rx * rz * (1.0 - cos) + ry * sin,
+            0.,
+            ry * rx * (1.0 - cos) + rz * sin,
+            cos + ry * ry * (1.0 - cos),
+            ry * rz * (1.0 - cos) - rx * sin,
+            0.,
+            rz * rx * (1.0 - cos) - ry * sin,
+            rz * ry * (1.0 - cos) + rx * sin,
+            cos + rz * rz * (1.0 - cos),
+            0.,
+            0.,
+            0.,
+            0.,
+            1.,
+        ]);
+        self * res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_fixed_matmul() {
+        let mat1 = Mat3::from_data([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let res = mat1 * mat1;
+        assert_eq!([30, 36, 42, 66, 81, 96, 102, 126, 150], res.data);
+        assert_eq!(mat1 * Vec3::new(1, 0, 1), Vec3::new(4, 10, 16));
+        assert_eq!(mat1.transpose().get(0, 1), 4);
+    }
+
+    #[test]
+    pub fn test_fixed_matches_matrix() {
+        let mat = Mat4::identity()
+            .translate(0.4, -0.4, 0.0)
+            .rotate(0., 0., 1., 90f32.to_radians())
+            .sscale(0.5);
+        let matrix = Matrix::<f32>::identity4()
+            .translate4(0.4, -0.4, 0.0)
+            .rot90(0., 0., 1.0)
+            .sscale4(0.5);
+        assert!(Matrix::from(mat) == matrix);
+        assert_eq!(Mat4::from(&matrix), mat);
+        let v = Vec4::new(1., 2., 3., 1.);
+        let res = &matrix * &Matrix::from(v);
+        assert_eq!(mat * v, Vec4::from(&res));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::types::linalg::matrix::Zero;
+use std::ops::{Add, Mul};
+
+//Side length of the square tiles, 3 tiles of 64x64 f32 fit into a 64KiB L1/L2 slice
+pub const BLOCK_SIZE: usize = 64;
+
+//out = a * b for row-major a (rows x k) and b (k x n), rows is inferred from out
+pub fn blocked_mul<T>(a: &[T], b: &[T], out: &mut [T], k: usize, n: usize)
+where
+    T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy,
+{
+    let m = out.len() / n;
+    debug_assert!(m * n == out.len() && a.len() == m * k && b.len() == k * n);
+    for t in out.iter_mut() {
+        *t = T::zero();
+    }
+    for i0 in (0..m).step_by(BLOCK_SIZE) {
+        let i_max = (i0 + BLOCK_SIZE).min(m);
+        for p0 in (0..k).step_by(BLOCK_SIZE) {
+            let p_max = (p0 + BLOCK_SIZE).min(k);
+            for j0 in (0..n).step_by(BLOCK_SIZE) {
+                let j_max = (j0 + BLOCK_SIZE).min(n);
+                for i in i0..i_max {
+                    let out_row = &mut out[i * n + j0..i * n + j_max];
+                    for p in p0..p_max {
+                        let a_ip = a[i * k + p];
+                        let b_row = &b[p * n + j0..p * n + j_max];
+                        for (o, &b_pj) in out_row.iter_mut().zip(b_row.iter()) {
+                            *o = *o + a_ip * b_pj;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+//Splits the output into bands of BLOCK_SIZE rows that are multiplied on the rayon thread pool
+#[cfg(feature = "parallel")]
+pub fn par_blocked_mul<T>(a: &[T], b: &[T], out: &mut [T], k: usize, n: usize)
+where
+    T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy + Send + Sync,
+{
+    use rayon::prelude::*;
+    out.par_chunks_mut(BLOCK_SIZE * n)
+        .enumerate()
+        .for_each(|(band, out_band)| {
+            let i0 = band * BLOCK_SIZE;
+            let rows = out_band.len() / n;
+            blocked_mul(&a[i0 * k..(i0 + rows) * k], b, out_band, k, n);
+        });
+}
@@ -1,29 +1,68 @@
 use crate::types::linalg::dimension::Dimension;
-use std::ops::{Add, Mul, Sub};
+use crate::types::linalg::matmul;
+use crate::types::linalg::matrix_view::MatrixView;
+use crate::types::linalg::storage_order::{ColumnMajor, RowMajor, StorageOrder};
+use crate::types::linalg::vector::Vec3;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MatrixError {
+    DimensionMismatch { left: Dimension, right: Dimension },
+    DataLength { expected: usize, actual: usize },
+    NotSquare(Dimension),
+    Underdetermined(Dimension),
+    Singular,
+    NotPositiveDefinite,
+}
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { left, right } => write!(
+                f,
+                "Dimension mismatch: {}x{} and {}x{}",
+                left.rows, left.columns, right.rows, right.columns
+            ),
+            MatrixError::DataLength { expected, actual } => write!(
+                f,
+                "Data length mismatch: expected {} elements, got {}",
+                expected, actual
+            ),
+            MatrixError::NotSquare(d) => {
+                write!(f, "Matrix is not square: {}x{}", d.rows, d.columns)
+            }
+            MatrixError::Underdetermined(d) => write!(
+                f,
+                "System is underdetermined: {}x{} has fewer rows than columns",
+                d.rows, d.columns
+            ),
+            MatrixError::Singular => write!(f, "Matrix is singular"),
+            MatrixError::NotPositiveDefinite => write!(f, "Matrix is not positive definite"),
+        }
+    }
+}
+impl std::error::Error for MatrixError {}
 
 #[derive(Clone, PartialEq)]
 #[repr(C)]
-pub struct Matrix<T> {
+pub struct Matrix<T, O = RowMajor> {
     pub data: Vec<T>,
     pub dimension: Dimension,
+    order: PhantomData<O>,
 }
 impl Matrix<f32> {
     pub fn zero4() -> Matrix<f32> {
-        Matrix::from_data(vec![0.; 16], Dimension::new(4, 4))
+        Matrix::zero(Dimension::new(4, 4))
     }
     pub fn identity4() -> Matrix<f32> {
-        Matrix::from_data(
-            vec![
-                1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
-            ],
-            Dimension::new(4, 4),
-        )
+        Matrix::identity(4)
     }
     pub fn scale4(self, s1: f32, s2: f32, s3: f32) -> Matrix<f32> {
         let mut res = Matrix::identity4();
-        res.data[res.dimension.to_index(0, 0)] = s1;
-        res.data[res.dimension.to_index(1, 1)] = s2;
-        res.data[res.dimension.to_index(2, 2)] = s3;
+        res[(0, 0)] = s1;
+        res[(1, 1)] = s2;
+        res[(2, 2)] = s3;
         self.mul(res)
     }
     pub fn sscale4(self, s: f32) -> Matrix<f32> {
@@ -31,9 +70,9 @@ impl Matrix<f32> {
     }
     pub fn translate4(self, t1: f32, t2: f32, t3: f32) -> Matrix<f32> {
         let mut res = Matrix::identity4();
-        res.data[res.dimension.to_index(0, 3)] = t1;
-        res.data[res.dimension.to_index(1, 3)] = t2;
-        res.data[res.dimension.to_index(2, 3)] = t3;
+        res[(0, 3)] = t1;
+        res[(1, 3)] = t2;
+        res[(2, 3)] = t3;
         self.mul(res)
     }
     pub fn ttranslate4(self, t: f32) -> Matrix<f32> {
@@ -43,16 +82,16 @@ impl Matrix<f32> {
         let mut res = Matrix::zero4();
         let cos = theta.cos();
         let sin = theta.sin();
-        res.data[res.dimension.to_index(0, 0)] = cos + rx * rx * (1.0 - cos);
-        res.data[res.dimension.to_index(0, 1)] = rx * ry * (1.0 - cos) - rz * sin;
-        res.data[res.dimension.to_index(0, 2)] = rx * rz * (1.0 - cos) + ry * sin;
-        res.data[res.dimension.to_index(1, 0)] = ry * rx * (1.0 - cos) + rz * sin;
-        res.data[res.dimension.to_index(1, 1)] = cos + ry * ry * (1.0 - cos);
-        res.data[res.dimension.to_index(1, 2)] = ry * rz * (1.0 - cos) - rx * sin;
-        res.data[res.dimension.to_index(2, 0)] = rz * rx * (1.0 - cos) - ry * sin;
-        res.data[res.dimension.to_index(2, 1)] = rz * ry * (1.0 - cos) + rx * sin;
-        res.data[res.dimension.to_index(2, 2)] = cos + rz * rz * (1.0 - cos);
-        res.data[res.dimension.to_index(3, 3)] = 1.;
+        res[(0, 0)] = cos + rx * rx * (1.0 - cos);
+        res[(0, 1)] = rx * ry * (1.0 - cos) - rz * sin;
+        res[(0, 2)] = rx * rz * (1.0 - cos) + ry * sin;
+        res[(1, 0)] = ry * rx * (1.0 - cos) + rz * sin;
+        res[(1, 1)] = cos + ry * ry * (1.0 - cos);
+        res[(1, 2)] = ry * rz * (1.0 - cos) - rx * sin;
+        res[(2, 0)] = rz * rx * (1.0 - cos) - ry * sin;
+        res[(2, 1)] = rz * ry * (1.0 - cos) + rx * sin;
+        res[(2, 2)] = cos + rz * rz * (1.0 - cos);
+        res[(3, 3)] = 1.;
         self.mul(res)
     }
     pub fn rot90(self, rx: f32, ry: f32, rz: f32) -> Matrix<f32> {
@@ -64,12 +103,365 @@ impl Matrix<f32> {
     pub fn rot270(self, rx: f32, ry: f32, rz: f32) -> Matrix<f32> {
         self.rotate4(rx, ry, rz, 270.0f32.to_radians())
     }
+    //Projection and View
+    pub fn frustum4(
+        self,
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        near: f32,
+        far: f32,
+    ) -> Matrix<f32> {
+        debug_assert!(near > 0. && far > near);
+        let mut res = Matrix::zero4();
+        res[(0, 0)] = 2. * near / (right - left);
+        res[(0, 2)] = (right + left) / (right - left);
+        res[(1, 1)] = 2. * near / (top - bottom);
+        res[(1, 2)] = (top + bottom) / (top - bottom);
+        res[(2, 2)] = -(far + near) / (far - near);
+        res[(2, 3)] = -2. * far * near / (far - near);
+        res[(3, 2)] = -1.;
+        self.mul(res)
+    }
+    pub fn perspective4(self, fov: f32, aspect: f32, near: f32, far: f32) -> Matrix<f32> {
+        //fov is the vertical field of view in radians
+        let top = near * (fov / 2.).tan();
+        let right = top * aspect;
+        self.frustum4(-right, right, -top, top, near, far)
+    }
+    pub fn orthographic4(
+        self,
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        near: f32,
+        far: f32,
+    ) -> Matrix<f32> {
+        let mut res = Matrix::identity4();
+        res[(0, 0)] = 2. / (right - left);
+        res[(0, 3)] = -(right + left) / (right - left);
+        res[(1, 1)] = 2. / (top - bottom);
+        res[(1, 3)] = -(top + bottom) / (top - bottom);
+        res[(2, 2)] = -2. / (far - near);
+        res[(2, 3)] = -(far + near) / (far - near);
+        self.mul(res)
+    }
+    pub fn look_at4(self, eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> Matrix<f32> {
+        let normalize = |v: [f32; 3]| {
+            let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+            debug_assert!(len > 0.);
+            [v[0] / len, v[1] / len, v[2] / len]
+        };
+        let cross = |a: [f32; 3], b: [f32; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let dot = |a: [f32; 3], b: [f32; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        let forward = normalize([target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]]);
+        let side = normalize(cross(forward, up));
+        let up = cross(side, forward);
+        let mut res = Matrix::identity4();
+        for i in 0..3 {
+            res[(0, i)] = side[i];
+            res[(1, i)] = up[i];
+            res[(2, i)] = -forward[i];
+        }
+        res[(0, 3)] = -dot(side, eye);
+        res[(1, 3)] = -dot(up, eye);
+        res[(2, 3)] = dot(forward, eye);
+        self.mul(res)
+    }
+    //Determinant and Inverse
+    pub fn determinant(&self) -> f32 {
+        debug_assert!(self.dimension.rows == self.dimension.columns);
+        if self.dimension == Dimension::new(4, 4) {
+            return self.determinant4();
+        }
+        let n = self.dimension.rows;
+        let mut lu = self.data.clone();
+        let mut det = 1.;
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&a, &b| {
+                    lu[a * n + col]
+                        .abs()
+                        .partial_cmp(&lu[b * n + col].abs())
+                        .unwrap()
+                })
+                .unwrap();
+            if lu[pivot * n + col] == 0. {
+                return 0.;
+            }
+            if pivot != col {
+                for c in 0..n {
+                    lu.swap(pivot * n + c, col * n + c);
+                }
+                det = -det;
+            }
+            let p = lu[col * n + col];
+            det *= p;
+            for row in col + 1..n {
+                let factor = lu[row * n + col] / p;
+                for c in col..n {
+                    lu[row * n + c] -= factor * lu[col * n + c];
+                }
+            }
+        }
+        det
+    }
+    pub fn inverse(&self) -> Option<Matrix<f32>> {
+        debug_assert!(self.dimension.rows == self.dimension.columns);
+        if self.dimension == Dimension::new(4, 4) {
+            return self.inverse4();
+        }
+        //Gauss-Jordan elimination with partial pivoting on [A | I]
+        let n = self.dimension.rows;
+        let mut a = self.data.clone();
+        let mut inv = vec![0.; n * n];
+        let tolerances = self.pivot_tolerances();
+        for i in 0..n {
+            inv[i * n + i] = 1.;
+        }
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&x, &y| {
+                    a[x * n + col]
+                        .abs()
+                        .partial_cmp(&a[y * n + col].abs())
+                        .unwrap()
+                })
+                .unwrap();
+            if a[pivot * n + col].abs() <= tolerances[col] {
+                return None;
+            }
+            if pivot != col {
+                for c in 0..n {
+                    a.swap(pivot * n + c, col * n + c);
+                    inv.swap(pivot * n + c, col * n + c);
+                }
+            }
+            let p = a[col * n + col];
+            for c in 0..n {
+                a[col * n + c] /= p;
+                inv[col * n + c] /= p;
+            }
+            for row in 0..n {
+                if row == col {
+                    continue;
+                }
+                let factor = a[row * n + col];
+                if factor == 0. {
+                    continue;
+                }
+                for c in 0..n {
+                    a[row * n + c] -= factor * a[col * n + c];
+                    inv[row * n + c] -= factor * inv[col * n + c];
+                }
+            }
+        }
+        Some(Matrix::from_data(inv, self.dimension))
+    }
+    //Pivots at most this small count as zero. Relative to the largest entry of the pivot's column,
+    //so scaling a column, e.g. a large translation next to a tiny scale, doesn't change the outcome.
+    fn pivot_tolerances(&self) -> Vec<f32> {
+        let n = self.dimension.rows;
+        (0..n)
+            .map(|col| {
+                let max = (0..n).fold(0f32, |curr, row| curr.max(self.data[row * n + col].abs()));
+                max * n as f32 * f32::EPSILON
+            })
+            .collect()
+    }
+    //Whether the elimination of inverse hits a zero pivot, so that inverse4 rejects the same matrices
+    fn is_singular(&self) -> bool {
+        let n = self.dimension.rows;
+        let tolerances = self.pivot_tolerances();
+        let mut a = self.data.clone();
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&x, &y| {
+                    a[x * n + col]
+                        .abs()
+                        .partial_cmp(&a[y * n + col].abs())
+                        .unwrap()
+                })
+                .unwrap();
+            if a[pivot * n + col].abs() <= tolerances[col] {
+                return true;
+            }
+            for c in col..n {
+                a.swap(pivot * n + c, col * n + c);
+            }
+            let p = a[col * n + col];
+            for row in col + 1..n {
+                let factor = a[row * n + col] / p;
+                for c in col..n {
+                    a[row * n + c] -= factor * a[col * n + c];
+                }
+            }
+        }
+        false
+    }
+    pub fn determinant4(&self) -> f32 {
+        debug_assert!(self.dimension == Dimension::new(4, 4));
+        let m = &self.data;
+        let s0 = m[0] * m[5] - m[4] * m[1];
+        let s1 = m[0] * m[6] - m[4] * m[2];
+        let s2 = m[0] * m[7] - m[4] * m[3];
+        let s3 = m[1] * m[6] - m[5] * m[2];
+        let s4 = m[1] * m[7] - m[5] * m[3];
+        let s5 = m[2] * m[7] - m[6] * m[3];
+        let c5 = m[10] * m[15] - m[14] * m[11];
+        let c4 = m[9] * m[15] - m[13] * m[11];
+        let c3 = m[9] * m[14] - m[13] * m[10];
+        let c2 = m[8] * m[15] - m[12] * m[11];
+        let c1 = m[8] * m[14] - m[12] * m[10];
+        let c0 = m[8] * m[13] - m[12] * m[9];
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+    pub fn inverse4(&self) -> Option<Matrix<f32>> {
+        debug_assert!(self.dimension == Dimension::new(4, 4));
+        //Laplace expansion by complementary 2x2 minors
+        let m = &self.data;
+        let s0 = m[0] * m[5] - m[4] * m[1];
+        let s1 = m[0] * m[6] - m[4] * m[2];
+        let s2 = m[0] * m[7] - m[4] * m[3];
+        let s3 = m[1] * m[6] - m[5] * m[2];
+        let s4 = m[1] * m[7] - m[5] * m[3];
+        let s5 = m[2] * m[7] - m[6] * m[3];
+        let c5 = m[10] * m[15] - m[14] * m[11];
+        let c4 = m[9] * m[15] - m[13] * m[11];
+        let c3 = m[9] * m[14] - m[13] * m[10];
+        let c2 = m[8] * m[15] - m[12] * m[11];
+        let c1 = m[8] * m[14] - m[12] * m[10];
+        let c0 = m[8] * m[13] - m[12] * m[9];
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        let inv_det = 1. / det;
+        if self.is_singular() || !inv_det.is_finite() {
+            return None;
+        }
+        let data = vec![
+            (m[5] * c5 - m[6] * c4 + m[7] * c3) * inv_det,
+            (-m[1] * c5 + m[2] * c4 - m[3] * c3) * inv_det,
+            (m[13] * s5 - m[14] * s4 + m[15] * s3) * inv_det,
+            (-m[9] * s5 + m[10] * s4 - m[11] * s3) * inv_det,
+            (-m[4] * c5 + m[6] * c2 - m[7] * c1) * inv_det,
+            (m[0] * c5 - m[2] * c2 + m[3] * c1) * inv_det,
+            (-m[12] * s5 + m[14] * s2 - m[15] * s1) * inv_det,
+            (m[8] * s5 - m[10] * s2 + m[11] * s1) * inv_det,
+            (m[4] * c4 - m[5] * c2 + m[7] * c0) * inv_det,
+            (-m[0] * c4 + m[1] * c2 - m[3] * c0) * inv_det,
+            (m[12] * s4 - m[13] * s2 + m[15] * s0) * inv_det,
+            (-m[8] * s4 + m[9] * s2 - m[11] * s0) * inv_det,
+            (-m[4] * c3 + m[5] * c1 - m[6] * c0) * inv_det,
+            (m[0] * c3 - m[1] * c1 + m[2] * c0) * inv_det,
+            (-m[12] * s3 + m[13] * s1 - m[14] * s0) * inv_det,
+            (m[8] * s3 - m[9] * s1 + m[10] * s0) * inv_det,
+        ];
+        Some(Matrix::from_data(data, self.dimension))
+    }
+    //Applies a 4x4 transform to a point (w = 1), dividing by the resulting w if it isn't 1
+    pub fn transform_point(&self, point: Vec3<f32>) -> Vec3<f32> {
+        debug_assert!(self.dimension == Dimension::new(4, 4));
+        let m = &self.data;
+        let row = |r: usize| {
+            m[4 * r] * point.x + m[4 * r + 1] * point.y + m[4 * r + 2] * point.z + m[4 * r + 3]
+        };
+        let w = row(3);
+        let res = Vec3::new(row(0), row(1), row(2));
+        if w == 1. || w == 0. {
+            res
+        } else {
+            res * (1. / w)
+        }
+    }
+    //Applies the linear part of a 4x4 transform to a direction (w = 0)
+    pub fn transform_vector(&self, vector: Vec3<f32>) -> Vec3<f32> {
+        debug_assert!(self.dimension == Dimension::new(4, 4));
+        let m = &self.data;
+        let row =
+            |r: usize| m[4 * r] * vector.x + m[4 * r + 1] * vector.y + m[4 * r + 2] * vector.z;
+        Vec3::new(row(0), row(1), row(2))
+    }
 }
 impl<T: Copy> Matrix<T> {
     pub fn from_data(data: Vec<T>, dimension: Dimension) -> Self {
-        debug_assert!(!data.is_empty());
-        Matrix { dimension, data }
+        match Matrix::try_from_data(data, dimension) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+    pub fn try_from_data(data: Vec<T>, dimension: Dimension) -> Result<Self, MatrixError> {
+        Matrix::try_from_raw(data, dimension)
+    }
+    pub fn from_closure<F>(closure: F, dimension: Dimension) -> Matrix<T>
+    where
+        F: Fn(usize) -> T,
+    {
+        let mut data = Vec::with_capacity(dimension.rows * dimension.columns);
+        for index in dimension.iter() {
+            data.push(closure(index));
+        }
+        Matrix::from_raw(data, dimension)
     }
+    pub fn to_column_major(&self) -> Matrix<T, ColumnMajor> {
+        self.transpose().reinterpret_transposed()
+    }
+}
+impl<T: Copy> Matrix<T, ColumnMajor> {
+    pub fn from_column_major_data(data: Vec<T>, dimension: Dimension) -> Self {
+        match Matrix::try_from_column_major_data(data, dimension) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+    pub fn try_from_column_major_data(
+        data: Vec<T>,
+        dimension: Dimension,
+    ) -> Result<Self, MatrixError> {
+        Matrix::try_from_raw(data, dimension)
+    }
+    pub fn to_row_major(&self) -> Matrix<T> {
+        self.transpose().reinterpret_transposed()
+    }
+}
+impl<T, O: StorageOrder> Matrix<T, O> {
+    #[inline(always)]
+    fn from_raw(data: Vec<T>, dimension: Dimension) -> Self {
+        Matrix {
+            data,
+            dimension,
+            order: PhantomData,
+        }
+    }
+    fn try_from_raw(data: Vec<T>, dimension: Dimension) -> Result<Self, MatrixError> {
+        let expected = dimension.rows * dimension.columns;
+        if data.is_empty() || data.len() != expected {
+            Err(MatrixError::DataLength {
+                expected,
+                actual: data.len(),
+            })
+        } else {
+            Ok(Matrix::from_raw(data, dimension))
+        }
+    }
+    pub fn is_row_major(&self) -> bool {
+        O::ROW_MAJOR
+    }
+    //Zero-copy, the same buffer read in the other storage order is the transposed matrix
+    pub fn reinterpret_transposed(self) -> Matrix<T, O::Transposed> {
+        Matrix::from_raw(
+            self.data,
+            Dimension::new(self.dimension.columns, self.dimension.rows),
+        )
+    }
+}
+impl<T: Copy, O: StorageOrder> Matrix<T, O> {
     pub fn as_ptr(&self) -> *const T {
         self.data.as_ptr()
     }
@@ -81,164 +473,472 @@ impl<T: Copy> Matrix<T> {
             self.data[index] = closure(self.data[index], index);
         }
     }
-    pub fn from_closure<F>(closure: F, dimension: Dimension) -> Matrix<T>
+    pub fn closure_into_buffer<F>(&mut self, closure: F)
     where
         F: Fn(usize) -> T,
     {
-        let mut data = Vec::with_capacity(dimension.rows * dimension.columns);
-        for index in dimension.iter() {
-            data.push(closure(index));
+        for index in self.dimension.iter() {
+            self.data[index] = closure(index);
         }
-        Matrix { dimension, data }
     }
-    pub fn closure_into_buffer<F>(&mut self, closure: F)
+    pub fn row(&self, row: usize) -> MatrixView<'_, T> {
+        assert!(row < self.dimension.rows);
+        let stride = if O::ROW_MAJOR { 1 } else { self.dimension.rows };
+        MatrixView::new(
+            &self.data,
+            O::to_index(self.dimension, row, 0),
+            stride,
+            self.dimension.columns,
+        )
+    }
+    pub fn column(&self, column: usize) -> MatrixView<'_, T> {
+        assert!(column < self.dimension.columns);
+        let stride = if O::ROW_MAJOR {
+            self.dimension.columns
+        } else {
+            1
+        };
+        MatrixView::new(
+            &self.data,
+            O::to_index(self.dimension, 0, column),
+            stride,
+            self.dimension.rows,
+        )
+    }
+    pub fn iter_rows(&self) -> impl Iterator<Item = MatrixView<'_, T>> {
+        (0..self.dimension.rows).map(move |row| self.row(row))
+    }
+    pub fn iter_columns(&self) -> impl Iterator<Item = MatrixView<'_, T>> {
+        (0..self.dimension.columns).map(move |column| self.column(column))
+    }
+    pub fn map<U, F>(&self, closure: F) -> Matrix<U, O>
     where
-        F: Fn(usize) -> T,
+        F: Fn(T) -> U,
     {
-        for index in self.dimension.iter() {
-            self.data[index] = closure(index);
+        Matrix::from_raw(
+            self.data.iter().map(|&t| closure(t)).collect(),
+            self.dimension,
+        )
+    }
+    pub fn zip_with<U, F>(&self, other: &Matrix<T, O>, closure: F) -> Matrix<U, O>
+    where
+        F: Fn(T, T) -> U,
+    {
+        if let Err(e) = check_same_dimension(self.dimension, other.dimension) {
+            panic!("{}", e);
         }
+        Matrix::from_raw(
+            self.data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(&t1, &t2)| closure(t1, t2))
+                .collect(),
+            self.dimension,
+        )
+    }
+    pub fn transpose(&self) -> Matrix<T, O> {
+        let output_dimension = Dimension::new(self.dimension.columns, self.dimension.rows);
+        let data = output_dimension
+            .iter()
+            .map(|index| {
+                let (row, colm) = O::to_xy(output_dimension, index);
+                self[(colm, row)]
+            })
+            .collect();
+        Matrix::from_raw(data, output_dimension)
+    }
+}
+impl<T, O: StorageOrder> Index<(usize, usize)> for Matrix<T, O> {
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        assert!(
+            row < self.dimension.rows && column < self.dimension.columns,
+            "Index ({}, {}) out of bounds for a {}x{} matrix",
+            row,
+            column,
+            self.dimension.rows,
+            self.dimension.columns
+        );
+        &self.data[O::to_index(self.dimension, row, column)]
+    }
+}
+impl<T, O: StorageOrder> IndexMut<(usize, usize)> for Matrix<T, O> {
+    #[inline(always)]
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            row < self.dimension.rows && column < self.dimension.columns,
+            "Index ({}, {}) out of bounds for a {}x{} matrix",
+            row,
+            column,
+            self.dimension.rows,
+            self.dimension.columns
+        );
+        let index = O::to_index(self.dimension, row, column);
+        &mut self.data[index]
+    }
+}
+impl<T: fmt::Display, O: StorageOrder> fmt::Display for Matrix<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted: Vec<String> = self
+            .data
+            .iter()
+            .map(|t| match f.precision() {
+                Some(precision) => format!("{:.*}", precision, t),
+                None => format!("{}", t),
+            })
+            .collect();
+        let width = formatted.iter().map(|s| s.len()).max().unwrap_or(0);
+        for row in 0..self.dimension.rows {
+            write!(f, "[")?;
+            for column in 0..self.dimension.columns {
+                if column != 0 {
+                    write!(f, " ")?;
+                }
+                let s = &formatted[O::to_index(self.dimension, row, column)];
+                write!(f, "{:>width$}", s, width = width)?;
+            }
+            write!(f, "]")?;
+            if row + 1 != self.dimension.rows {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl<T: fmt::Debug + Copy, O: StorageOrder> fmt::Debug for Matrix<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Matrix{}x{} ",
+            self.dimension.rows, self.dimension.columns
+        )?;
+        f.debug_list()
+            .entries(self.iter_rows().map(|row| row.to_vec()))
+            .finish()
+    }
+}
+//Matrix Negation
+impl<T: Neg<Output = T> + Copy, O: StorageOrder> Neg for Matrix<T, O> {
+    type Output = Matrix<T, O>;
+    fn neg(mut self) -> Self::Output {
+        self.apply_closure(|t, _| -t);
+        self
+    }
+}
+impl<T: Neg<Output = T> + Copy, O: StorageOrder> Neg for &Matrix<T, O> {
+    type Output = Matrix<T, O>;
+    fn neg(self) -> Self::Output {
+        self.map(|t| -t)
     }
 }
 //Matrix Matrix Addition and Subtraction
-impl<T: Add<T, Output = T> + Copy> Add for Matrix<T> {
-    type Output = Matrix<T>;
-    fn add(mut self, other: Matrix<T>) -> Self::Output {
-        debug_assert!(self.dimension == other.dimension);
+fn check_same_dimension(left: Dimension, right: Dimension) -> Result<(), MatrixError> {
+    if left == right {
+        Ok(())
+    } else {
+        Err(MatrixError::DimensionMismatch { left, right })
+    }
+}
+impl<T: Add<T, Output = T> + Copy, O: StorageOrder> Matrix<T, O> {
+    pub fn checked_add(&self, other: &Matrix<T, O>) -> Result<Matrix<T, O>, MatrixError> {
+        check_same_dimension(self.dimension, other.dimension)?;
+        Ok(self.zip_with(other, |t1, t2| t1 + t2))
+    }
+}
+impl<T: Sub<T, Output = T> + Copy, O: StorageOrder> Matrix<T, O> {
+    pub fn checked_sub(&self, other: &Matrix<T, O>) -> Result<Matrix<T, O>, MatrixError> {
+        check_same_dimension(self.dimension, other.dimension)?;
+        Ok(self.zip_with(other, |t1, t2| t1 - t2))
+    }
+}
+impl<T: Add<T, Output = T> + Copy, O: StorageOrder> Add for Matrix<T, O> {
+    type Output = Matrix<T, O>;
+    fn add(mut self, other: Matrix<T, O>) -> Self::Output {
+        if let Err(e) = check_same_dimension(self.dimension, other.dimension) {
+            panic!("{}", e);
+        }
         self.apply_closure(|t, index| t + other.data[index]);
         self
     }
 }
-impl<'a, 'b, T: Add<T, Output = T> + Copy> Add<&'b Matrix<T>> for &'a Matrix<T> {
-    type Output = Matrix<T>;
-    fn add(self, other: &'b Matrix<T>) -> Self::Output {
-        debug_assert!(self.dimension == other.dimension);
-        Matrix::from_closure(|index| self.data[index] + other.data[index], self.dimension)
+impl<'a, 'b, T: Add<T, Output = T> + Copy, O: StorageOrder> Add<&'b Matrix<T, O>>
+    for &'a Matrix<T, O>
+{
+    type Output = Matrix<T, O>;
+    fn add(self, other: &'b Matrix<T, O>) -> Self::Output {
+        match self.checked_add(other) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
     }
 }
-impl<T: Sub<T, Output = T> + Copy> Sub for Matrix<T> {
-    type Output = Matrix<T>;
-    fn sub(mut self, other: Matrix<T>) -> Self::Output {
-        debug_assert!(self.dimension == other.dimension);
+impl<T: Sub<T, Output = T> + Copy, O: StorageOrder> Sub for Matrix<T, O> {
+    type Output = Matrix<T, O>;
+    fn sub(mut self, other: Matrix<T, O>) -> Self::Output {
+        if let Err(e) = check_same_dimension(self.dimension, other.dimension) {
+            panic!("{}", e);
+        }
         self.apply_closure(|t, index| t - other.data[index]);
         self
     }
 }
-impl<'a, 'b, T: Sub<T, Output = T> + Copy> Sub<&'b Matrix<T>> for &'a Matrix<T> {
-    type Output = Matrix<T>;
-    fn sub(self, other: &'b Matrix<T>) -> Self::Output {
-        debug_assert!(self.dimension == other.dimension);
-        Matrix::from_closure(|index| self.data[index] - other.data[index], self.dimension)
+impl<'a, 'b, T: Sub<T, Output = T> + Copy, O: StorageOrder> Sub<&'b Matrix<T, O>>
+    for &'a Matrix<T, O>
+{
+    type Output = Matrix<T, O>;
+    fn sub(self, other: &'b Matrix<T, O>) -> Self::Output {
+        match self.checked_sub(other) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
     }
 }
 //Matrix Scalar Addition and Subtraction and Multiplication
-impl<T: Add<T, Output = T> + Copy> Add<T> for Matrix<T> {
-    type Output = Matrix<T>;
+impl<T: Add<T, Output = T> + Copy, O: StorageOrder> Add<T> for Matrix<T, O> {
+    type Output = Matrix<T, O>;
     fn add(mut self, other: T) -> Self::Output {
         self.apply_closure(|t, _| t + other);
         self
     }
 }
-impl<'a, T: Add<T, Output = T> + Copy> Add<T> for &'a Matrix<T> {
-    type Output = Matrix<T>;
+impl<'a, T: Add<T, Output = T> + Copy, O: StorageOrder> Add<T> for &'a Matrix<T, O> {
+    type Output = Matrix<T, O>;
     fn add(self, other: T) -> Self::Output {
-        Matrix::from_closure(|index| self.data[index] + other, self.dimension)
+        self.map(|t| t + other)
     }
 }
-impl<T: Sub<T, Output = T> + Copy> Sub<T> for Matrix<T> {
-    type Output = Matrix<T>;
+impl<T: Sub<T, Output = T> + Copy, O: StorageOrder> Sub<T> for Matrix<T, O> {
+    type Output = Matrix<T, O>;
     fn sub(mut self, other: T) -> Self::Output {
         self.apply_closure(|t, _| t - other);
         self
     }
 }
-impl<'a, T: Sub<T, Output = T> + Copy> Sub<T> for &'a Matrix<T> {
-    type Output = Matrix<T>;
+impl<'a, T: Sub<T, Output = T> + Copy, O: StorageOrder> Sub<T> for &'a Matrix<T, O> {
+    type Output = Matrix<T, O>;
     fn sub(self, other: T) -> Self::Output {
-        Matrix::from_closure(|index| self.data[index] - other, self.dimension)
+        self.map(|t| t - other)
     }
 }
-impl<T: Mul<T, Output = T> + Copy> Mul<T> for Matrix<T> {
-    type Output = Matrix<T>;
+impl<T: Mul<T, Output = T> + Copy, O: StorageOrder> Mul<T> for Matrix<T, O> {
+    type Output = Matrix<T, O>;
     fn mul(mut self, other: T) -> Self::Output {
         self.apply_closure(|t, _| t * other);
         self
     }
 }
-impl<'a, T: Mul<T, Output = T> + Copy> Mul<T> for &'a Matrix<T> {
-    type Output = Matrix<T>;
+impl<'a, T: Mul<T, Output = T> + Copy, O: StorageOrder> Mul<T> for &'a Matrix<T, O> {
+    type Output = Matrix<T, O>;
     fn mul(self, other: T) -> Self::Output {
-        Matrix::from_closure(|index| self.data[index] * other, self.dimension)
+        self.map(|t| t * other)
+    }
+}
+impl<T: Div<T, Output = T> + Copy, O: StorageOrder> Div<T> for Matrix<T, O> {
+    type Output = Matrix<T, O>;
+    fn div(mut self, other: T) -> Self::Output {
+        self.apply_closure(|t, _| t / other);
+        self
+    }
+}
+impl<T: Div<T, Output = T> + Copy, O: StorageOrder> Div<T> for &Matrix<T, O> {
+    type Output = Matrix<T, O>;
+    fn div(self, other: T) -> Self::Output {
+        self.map(|t| t / other)
+    }
+}
+//Matrix Matrix Element-wise (Hadamard) Multiplication
+impl<T: Mul<T, Output = T> + Copy, O: StorageOrder> Matrix<T, O> {
+    pub fn hadamard(&self, other: &Matrix<T, O>) -> Matrix<T, O> {
+        self.zip_with(other, |t1, t2| t1 * t2)
     }
 }
 //Matrix Matrix Multiplication
 pub trait Zero: Sized + Add<Self, Output = Self> {
     fn zero() -> Self;
 }
-impl Zero for i32 {
-    #[inline(always)]
-    fn zero() -> Self {
-        0
-    }
+pub trait One: Sized + Mul<Self, Output = Self> {
+    fn one() -> Self;
 }
-impl Zero for f32 {
-    #[inline(always)]
-    fn zero() -> Self {
-        0.
+macro_rules! impl_zero_one {
+    ($zero: expr, $one: expr, $($t: ty),+) => {
+        $(
+            impl Zero for $t {
+                #[inline(always)]
+                fn zero() -> Self {
+                    $zero
+                }
+            }
+            impl One for $t {
+                #[inline(always)]
+                fn one() -> Self {
+                    $one
+                }
+            }
+        )+
+    };
+}
+impl_zero_one!(0, 1, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_zero_one!(0., 1., f32, f64);
+pub trait Real:
+    Zero
+    + One
+    + Copy
+    + PartialOrd
+    + Sub<Self, Output = Self>
+    + Div<Self, Output = Self>
+    + Neg<Output = Self>
+{
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn epsilon() -> Self;
+    fn from_usize(n: usize) -> Self;
+}
+macro_rules! impl_real {
+    ($($t: ident),+) => {
+        $(
+            impl Real for $t {
+                #[inline(always)]
+                fn sqrt(self) -> Self {
+                    $t::sqrt(self)
+                }
+                #[inline(always)]
+                fn abs(self) -> Self {
+                    $t::abs(self)
+                }
+                #[inline(always)]
+                fn epsilon() -> Self {
+                    $t::EPSILON
+                }
+                #[inline(always)]
+                fn from_usize(n: usize) -> Self {
+                    n as $t
+                }
+            }
+        )+
+    };
+}
+impl_real!(f32, f64);
+impl<T: Zero + Copy> Matrix<T> {
+    pub fn zero(dimension: Dimension) -> Matrix<T> {
+        Matrix::from_closure(|_| T::zero(), dimension)
     }
 }
-impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy> Mul<Matrix<T>> for Matrix<T> {
-    type Output = Matrix<T>;
-    fn mul(self, other: Matrix<T>) -> Self::Output {
-        debug_assert!(self.dimension.columns == other.dimension.rows);
-        let output_dimension = Dimension::new(self.dimension.rows, other.dimension.columns);
+impl<T: Zero + One + Copy> Matrix<T> {
+    pub fn identity(size: usize) -> Matrix<T> {
+        let dimension = Dimension::new(size, size);
         Matrix::from_closure(
             |index| {
-                let (row, colm) = output_dimension.to_xy(index);
-                (0..self.dimension.columns).fold(Zero::zero(), |curr, i| {
-                    curr + self.data[self.dimension.to_index(row, i)]
-                        * other.data[other.dimension.to_index(i, colm)]
-                })
+                let (row, colm) = dimension.to_xy(index);
+                if row == colm {
+                    T::one()
+                } else {
+                    T::zero()
+                }
             },
-            output_dimension,
+            dimension,
         )
     }
 }
-impl<'a, 'b, T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy> Mul<&'a Matrix<T>>
-    for &'b Matrix<T>
+fn check_mul_dimension(left: Dimension, right: Dimension) -> Result<Dimension, MatrixError> {
+    if left.columns == right.rows {
+        Ok(Dimension::new(left.rows, right.columns))
+    } else {
+        Err(MatrixError::DimensionMismatch { left, right })
+    }
+}
+impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy, O: StorageOrder> Matrix<T, O> {
+    //Column-major data is the row-major data of the transpose, so (AB)^T = B^T * A^T is computed
+    fn mul_into(m1: &Matrix<T, O>, m2: &Matrix<T, O>, out: &mut [T]) {
+        if O::ROW_MAJOR {
+            matmul::blocked_mul(
+                &m1.data,
+                &m2.data,
+                out,
+                m1.dimension.columns,
+                m2.dimension.columns,
+            );
+        } else {
+            matmul::blocked_mul(
+                &m2.data,
+                &m1.data,
+                out,
+                m2.dimension.rows,
+                m1.dimension.rows,
+            );
+        }
+    }
+    pub fn checked_mul(&self, other: &Matrix<T, O>) -> Result<Matrix<T, O>, MatrixError> {
+        let output_dimension = check_mul_dimension(self.dimension, other.dimension)?;
+        let mut data = vec![T::zero(); output_dimension.rows * output_dimension.columns];
+        Matrix::mul_into(self, other, &mut data);
+        Ok(Matrix::from_raw(data, output_dimension))
+    }
+}
+impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy, O: StorageOrder> Mul<Matrix<T, O>>
+    for Matrix<T, O>
 {
-    type Output = Matrix<T>;
-    fn mul(self, other: &'a Matrix<T>) -> Self::Output {
-        debug_assert!(self.dimension.columns == other.dimension.rows);
-        let output_dimension = Dimension::new(self.dimension.rows, other.dimension.columns);
-        Matrix::from_closure(
-            |index| {
-                let (row, colm) = output_dimension.to_xy(index);
-                (0..self.dimension.columns).fold(Zero::zero(), |curr, i| {
-                    curr + self.data[self.dimension.to_index(row, i)]
-                        * other.data[other.dimension.to_index(i, colm)]
-                })
-            },
-            output_dimension,
-        )
+    type Output = Matrix<T, O>;
+    fn mul(self, other: Matrix<T, O>) -> Self::Output {
+        &self * &other
+    }
+}
+impl<'a, 'b, T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy, O: StorageOrder>
+    Mul<&'a Matrix<T, O>> for &'b Matrix<T, O>
+{
+    type Output = Matrix<T, O>;
+    fn mul(self, other: &'a Matrix<T, O>) -> Self::Output {
+        match self.checked_mul(other) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
     }
 }
 //Matrix Matrix Buffered Multiplication
-impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy> Matrix<T> {
-    pub fn buffered_mul(&mut self, m1: &Matrix<T>, m2: &Matrix<T>) {
-        debug_assert_eq!(m1.dimension.columns, m2.dimension.rows);
-        debug_assert!(
-            self.dimension.rows == m1.dimension.rows
-                && self.dimension.columns == m2.dimension.columns
-        );
-        let output_dimension = self.dimension;
-        self.closure_into_buffer(|index| {
-            let (row, colm) = output_dimension.to_xy(index);
-            (0..m1.dimension.columns).fold(Zero::zero(), |curr, i| {
-                curr + m1.data[m1.dimension.to_index(row, i)]
-                    * m2.data[m2.dimension.to_index(i, colm)]
-            })
-        });
+impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy, O: StorageOrder> Matrix<T, O> {
+    pub fn buffered_mul(&mut self, m1: &Matrix<T, O>, m2: &Matrix<T, O>) {
+        if let Err(e) = self.try_buffered_mul(m1, m2) {
+            panic!("{}", e);
+        }
+    }
+    pub fn try_buffered_mul(
+        &mut self,
+        m1: &Matrix<T, O>,
+        m2: &Matrix<T, O>,
+    ) -> Result<(), MatrixError> {
+        check_same_dimension(
+            self.dimension,
+            check_mul_dimension(m1.dimension, m2.dimension)?,
+        )?;
+        Matrix::mul_into(m1, m2, &mut self.data);
+        Ok(())
+    }
+}
+#[cfg(feature = "parallel")]
+impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy + Send + Sync, O: StorageOrder>
+    Matrix<T, O>
+{
+    pub fn checked_par_mul(&self, other: &Matrix<T, O>) -> Result<Matrix<T, O>, MatrixError> {
+        let output_dimension = check_mul_dimension(self.dimension, other.dimension)?;
+        let mut data = vec![T::zero(); output_dimension.rows * output_dimension.columns];
+        if O::ROW_MAJOR {
+            matmul::par_blocked_mul(
+                &self.data,
+                &other.data,
+                &mut data,
+                self.dimension.columns,
+                output_dimension.columns,
+            );
+        } else {
+            matmul::par_blocked_mul(
+                &other.data,
+                &self.data,
+                &mut data,
+                other.dimension.rows,
+                self.dimension.rows,
+            );
+        }
+        Ok(Matrix::from_raw(data, output_dimension))
     }
 }
 #[cfg(test)]
@@ -251,4 +951,300 @@ mod tests {
         let res = &matrix1 * &matrix1;
         debug_assert!(vec![30, 36, 42, 66, 81, 96, 102, 126, 150] == res.data);
     }
+
+    #[test]
+    pub fn test_generic_constructors() {
+        let identity = Matrix::<u32>::identity(3);
+        assert!(identity.data == vec![1, 0, 0, 0, 1, 0, 0, 0, 1]);
+        let matrix1 = Matrix::from_data(vec![1i64, -2, 3, 4, 5, 6, 7, 8, 9], Dimension::new(3, 3));
+        assert!(&matrix1 * &Matrix::identity(3) == matrix1);
+        let zero = Matrix::<f64>::zero(Dimension::new(2, 3));
+        assert!(zero.data == vec![0.; 6]);
+        let matrix2 = Matrix::from_data(vec![0.5f64, 1.5, 2., 4.], Dimension::new(2, 2));
+        assert!((&matrix2 * &Matrix::identity(2)).data == matrix2.data);
+        assert!(Matrix::<f32>::identity4() == Matrix::identity(4));
+    }
+
+    //Reference triple loop to check the blocked kernel against
+    fn naive_mul(m1: &Matrix<f32>, m2: &Matrix<f32>) -> Matrix<f32> {
+        let dimension = Dimension::new(m1.dimension.rows, m2.dimension.columns);
+        Matrix::from_closure(
+            |index| {
+                let (row, column) = dimension.to_xy(index);
+                (0..m1.dimension.columns).fold(0., |curr, i| curr + m1[(row, i)] * m2[(i, column)])
+            },
+            dimension,
+        )
+    }
+
+    #[test]
+    pub fn test_blocked_matmul() {
+        //Sizes that are not multiples of the block size exercise the edge blocks
+        let matrix1 = Matrix::from_closure(|i| (i % 13) as f32 - 6., Dimension::new(70, 130));
+        let matrix2 = Matrix::from_closure(|i| (i % 7) as f32 * 0.5, Dimension::new(130, 67));
+        let expected = naive_mul(&matrix1, &matrix2);
+        assert!(matrix1.checked_mul(&matrix2).unwrap() == expected);
+        let mut buffer = Matrix::zero(Dimension::new(70, 67));
+        buffer.buffered_mul(&matrix1, &matrix2);
+        assert!(buffer == expected);
+        #[cfg(feature = "parallel")]
+        assert!(matrix1.checked_par_mul(&matrix2).unwrap() == expected);
+    }
+
+    #[test]
+    pub fn test_column_major() {
+        let row_major = Matrix::from_data(vec![1, 2, 3, 4, 5, 6], Dimension::new(2, 3));
+        let column_major = row_major.to_column_major();
+        assert!(!column_major.is_row_major());
+        assert!(column_major.data == vec![1, 4, 2, 5, 3, 6]);
+        assert_eq!(column_major[(1, 0)], 4);
+        assert_eq!(column_major.row(1).to_vec(), vec![4, 5, 6]);
+        assert_eq!(column_major.column(2).to_vec(), vec![3, 6]);
+        assert!(column_major.to_row_major() == row_major);
+        assert_eq!(format!("{}", column_major), format!("{}", row_major));
+        //Reinterpreting the buffer in the other order is the transpose
+        let transposed = row_major.clone().reinterpret_transposed();
+        assert!(transposed.dimension == Dimension::new(3, 2));
+        assert!(transposed.to_row_major() == row_major.transpose());
+
+        let other = Matrix::from_data(vec![1, 0, 2, 1, 0, 3], Dimension::new(3, 2));
+        let expected = &row_major * &other;
+        let res = &column_major * &other.to_column_major();
+        assert!(res.to_row_major() == expected);
+        let mut buffer = Matrix::from_column_major_data(vec![0; 4], Dimension::new(2, 2));
+        buffer.buffered_mul(&column_major, &other.to_column_major());
+        assert!(buffer == res);
+        assert!((&column_major + &column_major).to_row_major() == &row_major + &row_major);
+        assert!(column_major.transpose().to_row_major() == row_major.transpose());
+    }
+
+    #[test]
+    pub fn test_checked_ops() {
+        let matrix1 = Matrix::from_data(vec![1, 2, 3, 4, 5, 6], Dimension::new(2, 3));
+        let matrix2 = Matrix::from_data(vec![1, 2, 3, 4, 5, 6], Dimension::new(3, 2));
+        assert!(matrix1.checked_add(&matrix1).unwrap().data == vec![2, 4, 6, 8, 10, 12]);
+        assert_eq!(
+            matrix1.checked_add(&matrix2).err(),
+            Some(MatrixError::DimensionMismatch {
+                left: Dimension::new(2, 3),
+                right: Dimension::new(3, 2)
+            })
+        );
+        assert!(matrix1.checked_sub(&matrix2).is_err());
+        assert!(matrix1.checked_mul(&matrix1).is_err());
+        let res = matrix1.checked_mul(&matrix2).unwrap();
+        assert!(res.data == vec![22, 28, 49, 64]);
+        let mut buffer = Matrix::from_data(vec![0; 4], Dimension::new(2, 2));
+        assert!(buffer.try_buffered_mul(&matrix1, &matrix2).is_ok());
+        assert!(buffer == res);
+        assert!(buffer.try_buffered_mul(&matrix2, &matrix1).is_err());
+        assert_eq!(
+            Matrix::try_from_data(vec![1, 2, 3], Dimension::new(2, 2)).err(),
+            Some(MatrixError::DataLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_index_and_views() {
+        let mut matrix1 = Matrix::from_data(vec![1, 2, 3, 4, 5, 6], Dimension::new(2, 3));
+        assert_eq!(matrix1[(1, 0)], 4);
+        matrix1[(1, 0)] = 7;
+        assert_eq!(matrix1.data[3], 7);
+        assert_eq!(matrix1.row(1).to_vec(), vec![7, 5, 6]);
+        assert_eq!(matrix1.column(2).to_vec(), vec![3, 6]);
+        assert_eq!(matrix1.column(1)[1], 5);
+        let sums: Vec<i32> = matrix1.iter_rows().map(|row| row.iter().sum()).collect();
+        assert_eq!(sums, vec![6, 18]);
+        assert_eq!(matrix1.iter_columns().count(), 3);
+    }
+
+    #[test]
+    pub fn test_elementwise_ops() {
+        let matrix1 = Matrix::from_data(vec![1, 2, 3, 4], Dimension::new(2, 2));
+        assert!((-&matrix1).data == vec![-1, -2, -3, -4]);
+        assert!((&matrix1 / 2).data == vec![0, 1, 1, 2]);
+        assert!(matrix1.hadamard(&matrix1).data == vec![1, 4, 9, 16]);
+        assert!(matrix1.map(|t| t as f32 * 0.5).data == vec![0.5, 1., 1.5, 2.]);
+        assert!(matrix1.zip_with(&matrix1, |t1, t2| t1 - t2).data == vec![0; 4]);
+    }
+
+    #[test]
+    pub fn test_format() {
+        let matrix1 = Matrix::from_data(vec![1, -20, 3, 4], Dimension::new(2, 2));
+        assert_eq!(format!("{}", matrix1), "[  1 -20]\n[  3   4]");
+        let matrix2 = Matrix::from_data(vec![0.5, 1.], Dimension::new(1, 2));
+        assert_eq!(format!("{:.2}", matrix2), "[0.50 1.00]");
+        assert_eq!(format!("{:?}", matrix1), "Matrix2x2 [[1, -20], [3, 4]]");
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_from_data_length() {
+        Matrix::from_data(vec![1., 2., 3.], Dimension::new(2, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_index_out_of_bounds() {
+        //(0, 5) lies inside the data of a 2x3 matrix but outside its columns
+        let matrix = Matrix::from_data(vec![1, 2, 3, 4, 5, 6], Dimension::new(2, 3));
+        let _ = matrix[(0, 5)];
+    }
+
+    fn approx_eq(m1: &Matrix<f32>, m2: &Matrix<f32>) -> bool {
+        m1.dimension == m2.dimension
+            && m1
+                .data
+                .iter()
+                .zip(m2.data.iter())
+                .all(|(a, b)| (a - b).abs() < 1e-4)
+    }
+
+    #[test]
+    pub fn test_transpose() {
+        let matrix1 = Matrix::from_data(vec![1, 2, 3, 4, 5, 6], Dimension::new(2, 3));
+        let res = matrix1.transpose();
+        assert!(res.dimension == Dimension::new(3, 2));
+        assert!(vec![1, 4, 2, 5, 3, 6] == res.data);
+        assert!(res.transpose() == matrix1);
+    }
+
+    #[test]
+    pub fn test_determinant() {
+        let matrix1 = Matrix::from_data(
+            vec![1., 2., 3., 4., 5., 6., 7., 8., 9.],
+            Dimension::new(3, 3),
+        );
+        assert!(matrix1.determinant().abs() < 1e-4);
+        let matrix2 = Matrix::from_data(
+            vec![1., 2., 3., 0., 1., 4., 5., 6., 0.],
+            Dimension::new(3, 3),
+        );
+        assert!((matrix2.determinant() - 1.).abs() < 1e-4);
+        let matrix3 = Matrix::<f32>::identity4()
+            .translate4(1., 2., 3.)
+            .scale4(2., 3., 4.);
+        assert!((matrix3.determinant4() - 24.).abs() < 1e-4);
+        assert!((matrix3.determinant() - 24.).abs() < 1e-4);
+    }
+
+    #[test]
+    pub fn test_inverse() {
+        let matrix1 = Matrix::from_data(
+            vec![1., 2., 3., 4., 5., 6., 7., 8., 9.],
+            Dimension::new(3, 3),
+        );
+        assert!(matrix1.inverse().is_none());
+        let matrix2 = Matrix::from_data(
+            vec![1., 2., 3., 0., 1., 4., 5., 6., 0.],
+            Dimension::new(3, 3),
+        );
+        let inv = matrix2.inverse().unwrap();
+        let expected = Matrix::from_data(
+            vec![-24., 18., 5., 20., -15., -4., -5., 4., 1.],
+            Dimension::new(3, 3),
+        );
+        assert!(approx_eq(&inv, &expected));
+
+        let matrix3 = Matrix::<f32>::identity4()
+            .translate4(0.4, -0.4, 0.0)
+            .rot90(0., 0., 1.0)
+            .sscale4(0.5);
+        let inv = matrix3.inverse4().unwrap();
+        assert!(approx_eq(&(&matrix3 * &inv), &Matrix::identity4()));
+        assert!(approx_eq(&(&inv * &matrix3), &Matrix::identity4()));
+        assert!(Matrix::<f32>::zero4().inverse4().is_none());
+        let rank3 = Matrix::from_data(
+            vec![
+                1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 2., 4., 6., 8.,
+            ],
+            Dimension::new(4, 4),
+        );
+        assert!(rank3.inverse4().is_none());
+    }
+
+    #[test]
+    pub fn test_inverse4_large_translation_and_small_scale() {
+        let transforms = [
+            Matrix::<f32>::identity4().translate4(60., 0., 0.),
+            Matrix::<f32>::identity4().translate4(1000., 0., 0.),
+            Matrix::<f32>::identity4().translate4(-25000., 1e4, 3e4),
+            Matrix::<f32>::identity4().look_at4([0., 0., 100.], [0., 0., 0.], [0., 1., 0.]),
+            Matrix::<f32>::identity4().sscale4(0.001),
+            Matrix::<f32>::identity4().sscale4(1e-6),
+            Matrix::<f32>::identity4()
+                .translate4(500., -200., 80.)
+                .sscale4(0.01),
+        ];
+        for m in transforms.iter() {
+            let inv = m.inverse().unwrap();
+            assert!(approx_eq(&(m * &inv), &Matrix::identity4()));
+        }
+        //Undoing a translation recovers the origin, e.g. the camera position of a view matrix
+        let view = Matrix::<f32>::identity4().translate4(-1000., 0., -54.);
+        let eye = view.inverse().unwrap();
+        assert_eq!((eye[(0, 3)], eye[(1, 3)], eye[(2, 3)]), (1000., 0., 54.));
+    }
+
+    #[test]
+    pub fn test_inverse_same_criterion_for_all_sizes() {
+        //Last row a rounded combination of the first two, numerically singular at every size
+        for n in 3..=5 {
+            let mut data: Vec<f32> = (0..n * n)
+                .map(|i| ((i * 7 + 3) % 11) as f32 * 0.37 - 1.3)
+                .collect();
+            for c in 0..n {
+                data[(n - 1) * n + c] = 0.1 * data[c] + 0.7 * data[n + c];
+            }
+            let m = Matrix::from_data(data, Dimension::new(n, n));
+            assert!(m.inverse().is_none(), "{}x{}", n, n);
+            if n == 4 {
+                assert!(m.inverse4().is_none());
+            }
+        }
+    }
+
+    fn project(m: &Matrix<f32>, p: [f32; 3]) -> [f32; 3] {
+        let res = m * &Matrix::from_data(vec![p[0], p[1], p[2], 1.], Dimension::new(4, 1));
+        [
+            res.data[0] / res.data[3],
+            res.data[1] / res.data[3],
+            res.data[2] / res.data[3],
+        ]
+    }
+
+    #[test]
+    pub fn test_projection() {
+        let perspective = Matrix::<f32>::identity4().perspective4(90f32.to_radians(), 2., 1., 10.);
+        let near = project(&perspective, [2., 1., -1.]);
+        assert!((near[0] - 1.).abs() < 1e-4 && (near[1] - 1.).abs() < 1e-4);
+        assert!((near[2] + 1.).abs() < 1e-4);
+        let far = project(&perspective, [0., 0., -10.]);
+        assert!((far[2] - 1.).abs() < 1e-4);
+
+        let orthographic = Matrix::<f32>::identity4().orthographic4(0., 4., 0., 2., 1., 3.);
+        let corner = project(&orthographic, [4., 2., -3.]);
+        assert!((corner[0] - 1.).abs() < 1e-4 && (corner[1] - 1.).abs() < 1e-4);
+        assert!((corner[2] - 1.).abs() < 1e-4);
+        let corner = project(&orthographic, [0., 0., -1.]);
+        assert!((corner[0] + 1.).abs() < 1e-4 && (corner[1] + 1.).abs() < 1e-4);
+        assert!((corner[2] + 1.).abs() < 1e-4);
+    }
+
+    #[test]
+    pub fn test_look_at() {
+        let eye = [1., 2., 3.];
+        let view = Matrix::<f32>::identity4().look_at4(eye, [1., 2., 0.], [0., 1., 0.]);
+        assert!(project(&view, eye).iter().all(|x| x.abs() < 1e-4));
+        let target = project(&view, [1., 2., 0.]);
+        assert!(target[0].abs() < 1e-4 && target[1].abs() < 1e-4);
+        assert!((target[2] + 3.).abs() < 1e-4);
+        let camera = view.inverse4().unwrap();
+        assert!((camera.data[3] - 1.).abs() < 1e-4);
+        assert!((camera.data[7] - 2.).abs() < 1e-4);
+        assert!((camera.data[11] - 3.).abs() < 1e-4);
+    }
 }
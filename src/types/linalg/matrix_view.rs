@@ -0,0 +1,42 @@
+use std::ops::Index;
+
+//Borrowed row or column of a Matrix, elements are `stride` apart in the underlying buffer
+#[derive(Clone, Copy)]
+pub struct MatrixView<'a, T> {
+    data: &'a [T],
+    offset: usize,
+    stride: usize,
+    len: usize,
+}
+impl<'a, T: Copy> MatrixView<'a, T> {
+    pub fn new(data: &'a [T], offset: usize, stride: usize, len: usize) -> Self {
+        assert!(len == 0 || offset + (len - 1) * stride < data.len());
+        MatrixView {
+            data,
+            offset,
+            stride,
+            len,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn iter(&self) -> impl Iterator<Item = T> + 'a {
+        let data = self.data;
+        let (offset, stride) = (self.offset, self.stride);
+        (0..self.len).map(move |i| data[offset + i * stride])
+    }
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+impl<T> Index<usize> for MatrixView<'_, T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        assert!(index < self.len);
+        &self.data[self.offset + index * self.stride]
+    }
+}
@@ -1,2 +1,10 @@
+pub mod bounding_volume;
+pub mod decomposition;
 pub mod dimension;
+pub mod fixed_matrix;
+pub mod matmul;
 pub mod matrix;
+pub mod matrix_view;
+pub mod quaternion;
+pub mod storage_order;
+pub mod vector;
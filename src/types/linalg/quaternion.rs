@@ -0,0 +1,267 @@
+use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::fixed_matrix::Mat4;
+use crate::types::linalg::matrix::Matrix;
+use crate::types::linalg::vector::Vec3;
+use std::ops::{Add, Mul, Neg};
+
+//q = w + xi + yj + zk
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Quaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+impl Quaternion {
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quaternion { x, y, z, w }
+    }
+    pub const fn identity() -> Self {
+        Quaternion::new(0., 0., 0., 1.)
+    }
+    pub fn from_axis_angle(axis: Vec3<f32>, theta: f32) -> Self {
+        let axis = axis.normalize();
+        let (sin, cos) = (theta / 2.).sin_cos();
+        Quaternion::new(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+    //Rotates around the x-axis first, then around the y-axis and finally around the z-axis
+    pub fn from_euler(rx: f32, ry: f32, rz: f32) -> Self {
+        Quaternion::from_axis_angle(Vec3::new(0., 0., 1.), rz)
+            * Quaternion::from_axis_angle(Vec3::new(0., 1., 0.), ry)
+            * Quaternion::from_axis_angle(Vec3::new(1., 0., 0.), rx)
+    }
+    //Rotation that turns the negative z-axis into forward and the y-axis towards up
+    pub fn look_rotation(forward: Vec3<f32>, up: Vec3<f32>) -> Self {
+        let z = -forward.normalize();
+        let x = up.cross(z).normalize();
+        let y = z.cross(x);
+        Quaternion::from_rotation_data([x.x, y.x, z.x, x.y, y.y, z.y, x.z, y.z, z.z])
+    }
+    pub fn from_matrix4(matrix: &Matrix<f32>) -> Self {
+        debug_assert!(matrix.dimension == Dimension::new(4, 4));
+        let m = &matrix.data;
+        Quaternion::from_rotation_data([m[0], m[1], m[2], m[4], m[5], m[6], m[8], m[9], m[10]])
+    }
+    //Expects the row-major upper left 3x3 block of a rotation matrix
+    fn from_rotation_data(m: [f32; 9]) -> Self {
+        let trace = m[0] + m[4] + m[8];
+        let q = if trace > 0. {
+            let s = (trace + 1.).sqrt() * 2.;
+            Quaternion::new(
+                (m[7] - m[5]) / s,
+                (m[2] - m[6]) / s,
+                (m[3] - m[1]) / s,
+                s / 4.,
+            )
+        } else if m[0] > m[4] && m[0] > m[8] {
+            let s = (1. + m[0] - m[4] - m[8]).sqrt() * 2.;
+            Quaternion::new(
+                s / 4.,
+                (m[1] + m[3]) / s,
+                (m[2] + m[6]) / s,
+                (m[7] - m[5]) / s,
+            )
+        } else if m[4] > m[8] {
+            let s = (1. + m[4] - m[0] - m[8]).sqrt() * 2.;
+            Quaternion::new(
+                (m[1] + m[3]) / s,
+                s / 4.,
+                (m[5] + m[7]) / s,
+                (m[2] - m[6]) / s,
+            )
+        } else {
+            let s = (1. + m[8] - m[0] - m[4]).sqrt() * 2.;
+            Quaternion::new(
+                (m[2] + m[6]) / s,
+                (m[5] + m[7]) / s,
+                s / 4.,
+                (m[3] - m[1]) / s,
+            )
+        };
+        q.normalize()
+    }
+    pub fn to_matrix4(self) -> Matrix<f32> {
+        Matrix::from(self.to_mat4())
+    }
+    pub fn to_mat4(self) -> Mat4<f32> {
+        let Quaternion { x, y, z, w } = self.normalize();
+        Mat4::from_data([
+            1. - 2. * (y * y + z * z),
+            2. * (x * y - z * w),
+            2. * (x * z + y * w),
+            0.,
+            2. * (x * y + z * w),
+            1. - 2. * (x * x + z * z),
+            2. * (y * z - x * w),
+            0.,
+            2. * (x * z - y * w),
+            2. * (y * z + x * w),
+            1. - 2. * (x * x + y * y),
+            0.,
+            0.,
+            0.,
+            0.,
+            1.,
+        ])
+    }
+    pub fn dot(self, other: Quaternion) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+    pub fn normalize(self) -> Quaternion {
+        let length = self.length();
+        debug_assert!(length > 0.);
+        self * (1. / length)
+    }
+    pub fn conjugate(self) -> Quaternion {
+        Quaternion::new(-self.x, -self.y, -self.z, self.w)
+    }
+    pub fn inverse(self) -> Quaternion {
+        self.conjugate() * (1. / self.dot(self))
+    }
+    pub fn rotate(self, v: Vec3<f32>) -> Vec3<f32> {
+        let axis = Vec3::new(self.x, self.y, self.z);
+        let t = axis.cross(v) * 2.;
+        v + t * self.w + axis.cross(t)
+    }
+    pub fn nlerp(self, other: Quaternion, t: f32) -> Quaternion {
+        //Take the shorter arc
+        let other = if self.dot(other) < 0. { -other } else { other };
+        (self * (1. - t) + other * t).normalize()
+    }
+    pub fn slerp(self, other: Quaternion, t: f32) -> Quaternion {
+        let mut cos = self.dot(other);
+        let other = if cos < 0. {
+            cos = -cos;
+            -other
+        } else {
+            other
+        };
+        if cos > 0.9995 {
+            //Nearly parallel, fall back to linear interpolation to avoid dividing by sin ~ 0
+            return self.nlerp(other, t);
+        }
+        let theta = cos.acos();
+        let sin = theta.sin();
+        (self * (((1. - t) * theta).sin() / sin) + other * ((t * theta).sin() / sin)).normalize()
+    }
+}
+impl Default for Quaternion {
+    fn default() -> Self {
+        Quaternion::identity()
+    }
+}
+//Composition, (q1 * q2) applies q2 first
+impl Mul for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, other: Quaternion) -> Self::Output {
+        Quaternion::new(
+            self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        )
+    }
+}
+impl Mul<f32> for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, other: f32) -> Self::Output {
+        Quaternion::new(
+            self.x * other,
+            self.y * other,
+            self.z * other,
+            self.w * other,
+        )
+    }
+}
+impl Mul<Vec3<f32>> for Quaternion {
+    type Output = Vec3<f32>;
+    fn mul(self, other: Vec3<f32>) -> Self::Output {
+        self.rotate(other)
+    }
+}
+impl Add for Quaternion {
+    type Output = Quaternion;
+    fn add(self, other: Quaternion) -> Self::Output {
+        Quaternion::new(
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+            self.w + other.w,
+        )
+    }
+}
+impl Neg for Quaternion {
+    type Output = Quaternion;
+    fn neg(self) -> Self::Output {
+        Quaternion::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_eq(v1: Vec3<f32>, v2: Vec3<f32>) -> bool {
+        (v1 - v2).length() < 1e-4
+    }
+
+    #[test]
+    pub fn test_quaternion_matrix_roundtrip() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0., 0., 2.), 90f32.to_radians());
+        let matrix = Matrix::<f32>::identity4().rot90(0., 0., 1.);
+        assert!(q
+            .to_matrix4()
+            .data
+            .iter()
+            .zip(matrix.data.iter())
+            .all(|(a, b)| (a - b).abs() < 1e-4));
+        let back = Quaternion::from_matrix4(&matrix);
+        assert!((back.dot(q).abs() - 1.).abs() < 1e-4);
+        for &q in [
+            Quaternion::from_euler(0.3, 2.9, -1.2),
+            Quaternion::from_euler(3.1, 0.1, 0.2),
+            Quaternion::from_euler(0.1, -3., 0.2),
+        ]
+        .iter()
+        {
+            let back = Quaternion::from_matrix4(&q.to_matrix4());
+            assert!((back.dot(q).abs() - 1.).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    pub fn test_quaternion_rotation() {
+        let q = Quaternion::from_euler(90f32.to_radians(), 0., 90f32.to_radians());
+        //x-rotation maps y to z, z-rotation leaves z
+        assert!(approx_eq(q * Vec3::new(0., 1., 0.), Vec3::new(0., 0., 1.)));
+        assert!(approx_eq(q * Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.)));
+        let look = Quaternion::look_rotation(Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.));
+        assert!(approx_eq(
+            look * Vec3::new(0., 0., -1.),
+            Vec3::new(1., 0., 0.)
+        ));
+        assert!(approx_eq(
+            look * Vec3::new(0., 1., 0.),
+            Vec3::new(0., 1., 0.)
+        ));
+        let inv = q.inverse() * q;
+        assert!((inv.w - 1.).abs() < 1e-4);
+    }
+
+    #[test]
+    pub fn test_slerp() {
+        let q1 = Quaternion::identity();
+        let q2 = Quaternion::from_axis_angle(Vec3::new(0., 1., 0.), 90f32.to_radians());
+        let half = q1.slerp(q2, 0.5);
+        let expected = Quaternion::from_axis_angle(Vec3::new(0., 1., 0.), 45f32.to_radians());
+        assert!((half.dot(expected) - 1.).abs() < 1e-4);
+        assert!((q1.slerp(q2, 1.).dot(q2) - 1.).abs() < 1e-4);
+        assert!((q1.nlerp(q2, 0.5).dot(expected) - 1.).abs() < 1e-4);
+        //Opposite sign represents the same rotation, interpolation takes the short arc
+        assert!((q1.slerp(-q2, 0.5).dot(expected).abs() - 1.).abs() < 1e-4);
+    }
+}
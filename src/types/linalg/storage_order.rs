@@ -0,0 +1,38 @@
+use crate::types::linalg::dimension::Dimension;
+
+//Memory layout of a Matrix, the data of a matrix read in the transposed order is its transpose
+pub trait StorageOrder: Copy + PartialEq + Default {
+    type Transposed: StorageOrder<Transposed = Self>;
+    const ROW_MAJOR: bool;
+    fn to_index(dimension: Dimension, row: usize, column: usize) -> usize;
+    fn to_xy(dimension: Dimension, index: usize) -> (usize, usize);
+}
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct RowMajor;
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct ColumnMajor;
+
+impl StorageOrder for RowMajor {
+    type Transposed = ColumnMajor;
+    const ROW_MAJOR: bool = true;
+    #[inline(always)]
+    fn to_index(dimension: Dimension, row: usize, column: usize) -> usize {
+        dimension.to_index(row, column)
+    }
+    #[inline(always)]
+    fn to_xy(dimension: Dimension, index: usize) -> (usize, usize) {
+        dimension.to_xy(index)
+    }
+}
+impl StorageOrder for ColumnMajor {
+    type Transposed = RowMajor;
+    const ROW_MAJOR: bool = false;
+    #[inline(always)]
+    fn to_index(dimension: Dimension, row: usize, column: usize) -> usize {
+        column * dimension.rows + row
+    }
+    #[inline(always)]
+    fn to_xy(dimension: Dimension, index: usize) -> (usize, usize) {
+        (index % dimension.rows, index / dimension.rows)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::matrix::{Matrix, Zero};
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[repr(C)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[repr(C)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[repr(C)]
+pub struct Vec4<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+}
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+impl<T> Vec4<T> {
+    pub const fn new(x: T, y: T, z: T, w: T) -> Self {
+        Vec4 { x, y, z, w }
+    }
+}
+impl<T: Copy> Vec2<T> {
+    pub fn extend(self, z: T) -> Vec3<T> {
+        Vec3::new(self.x, self.y, z)
+    }
+}
+impl<T: Copy> Vec3<T> {
+    pub fn extend(self, w: T) -> Vec4<T> {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+    pub fn truncate(self) -> Vec2<T> {
+        Vec2::new(self.x, self.y)
+    }
+}
+impl<T: Copy> Vec4<T> {
+    pub fn truncate(self) -> Vec3<T> {
+        Vec3::new(self.x, self.y, self.z)
+    }
+}
+impl<T: Mul<T, Output = T> + Sub<T, Output = T> + Copy> Vec3<T> {
+    pub fn cross(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+macro_rules! impl_vector {
+    ($vec: ident, $n: expr, $($field: ident),+) => {
+        impl<T: Copy> $vec<T> {
+            pub fn as_ptr(&self) -> *const T {
+                self as *const Self as *const T
+            }
+            pub fn to_array(self) -> [T; $n] {
+                [$(self.$field),+]
+            }
+        }
+        impl<T: Mul<T, Output = T> + Add<T, Output = T> + Zero + Copy> $vec<T> {
+            pub fn dot(self, other: $vec<T>) -> T {
+                T::zero() $(+ self.$field * other.$field)+
+            }
+            pub fn length_squared(self) -> T {
+                self.dot(self)
+            }
+        }
+        impl $vec<f32> {
+            pub fn length(self) -> f32 {
+                self.length_squared().sqrt()
+            }
+            pub fn normalize(self) -> $vec<f32> {
+                let length = self.length();
+                debug_assert!(length > 0.);
+                self * (1. / length)
+            }
+        }
+        impl $vec<f64> {
+            pub fn length(self) -> f64 {
+                self.length_squared().sqrt()
+            }
+            pub fn normalize(self) -> $vec<f64> {
+                let length = self.length();
+                debug_assert!(length > 0.);
+                self * (1. / length)
+            }
+        }
+        impl<T: Add<T, Output = T> + Copy> Add for $vec<T> {
+            type Output = $vec<T>;
+            fn add(self, other: $vec<T>) -> Self::Output {
+                $vec { $($field: self.$field + other.$field),+ }
+            }
+        }
+        impl<T: Sub<T, Output = T> + Copy> Sub for $vec<T> {
+            type Output = $vec<T>;
+            fn sub(self, other: $vec<T>) -> Self::Output {
+                $vec { $($field: self.$field - other.$field),+ }
+            }
+        }
+        impl<T: Mul<T, Output = T> + Copy> Mul<T> for $vec<T> {
+            type Output = $vec<T>;
+            fn mul(self, other: T) -> Self::Output {
+                $vec { $($field: self.$field * other),+ }
+            }
+        }
+        impl<T: Neg<Output = T> + Copy> Neg for $vec<T> {
+            type Output = $vec<T>;
+            fn neg(self) -> Self::Output {
+                $vec { $($field: -self.$field),+ }
+            }
+        }
+        //Conversion from and to column vectors
+        impl<T: Copy> From<$vec<T>> for Matrix<T> {
+            fn from(vec: $vec<T>) -> Self {
+                Matrix::from_data(vec![$(vec.$field),+], Dimension::new($n, 1))
+            }
+        }
+        impl<T: Copy> From<&Matrix<T>> for $vec<T> {
+            fn from(matrix: &Matrix<T>) -> Self {
+                assert!(matrix.data.len() == $n, "Expected a matrix with {} entries", $n);
+                let mut iter = matrix.data.iter();
+                $vec { $($field: *iter.next().unwrap()),+ }
+            }
+        }
+    };
+}
+impl_vector!(Vec2, 2, x, y);
+impl_vector!(Vec3, 3, x, y, z);
+impl_vector!(Vec4, 4, x, y, z, w);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_vector_ops() {
+        let v1 = Vec3::new(1., 0., 0.);
+        let v2 = Vec3::new(0., 1., 0.);
+        assert_eq!(v1.cross(v2), Vec3::new(0., 0., 1.));
+        assert_eq!(v1.dot(v2), 0.);
+        assert_eq!(Vec3::new(1, 2, 3).dot(Vec3::new(4, 5, 6)), 32);
+        assert_eq!(Vec2::new(3f32, 4.).length(), 5.);
+        assert!((Vec4::new(1f32, 1., 1., 1.).normalize().length() - 1.).abs() < 1e-6);
+        assert_eq!(Vec3::new(1, 2, 3) + Vec3::new(1, 1, 1), Vec3::new(2, 3, 4));
+        assert_eq!(-Vec2::new(1, 2) * 2, Vec2::new(-2, -4));
+    }
+
+    #[test]
+    pub fn test_vector_matrix_conversion() {
+        let v = Vec4::new(1., 2., 3., 4.);
+        let m = Matrix::from(v);
+        assert!(m.dimension == Dimension::new(4, 1));
+        assert_eq!(Vec4::from(&m), v);
+        assert_eq!(std::mem::size_of::<Vec4<f32>>(), 16);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_vector_from_wrong_matrix() {
+        let _ = Vec3::from(&Matrix::from(Vec4::new(1., 2., 3., 4.)));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod buffer;
 pub mod data;
+pub mod geometry;
 pub mod linalg;
+pub mod noise;
 pub mod shader;
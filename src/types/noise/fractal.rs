@@ -0,0 +1,119 @@
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+
+//Offsets between the noise samples that displace the domain, shared with shaders/noise.glsl
+pub const WARP_OFFSET_1: Vec3<f32> = Vec3::new(5.2, 1.3, 7.1);
+pub const WARP_OFFSET_2: Vec3<f32> = Vec3::new(1.7, 9.2, 3.4);
+
+//Fractal Brownian motion, sums octaves of a noise function and normalizes by the total amplitude
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Fbm {
+    pub octaves: u32,
+    pub frequency: f32,
+    pub lacunarity: f32,
+    pub gain: f32,
+}
+impl Default for Fbm {
+    fn default() -> Self {
+        Fbm {
+            octaves: 5,
+            frequency: 1.,
+            lacunarity: 2.,
+            gain: 0.5,
+        }
+    }
+}
+macro_rules! impl_fbm_sample {
+    ($name: ident, $vec: ident) => {
+        //Octave n is sampled with seed + n
+        pub fn $name<F: Fn($vec<f32>, u32) -> f32>(
+            &self,
+            noise: F,
+            p: $vec<f32>,
+            seed: u32,
+        ) -> f32 {
+            let mut sum = 0.;
+            let mut total = 0.;
+            let mut amplitude = 1.;
+            let mut frequency = self.frequency;
+            for octave in 0..self.octaves {
+                sum += amplitude * noise(p * frequency, seed.wrapping_add(octave));
+                total += amplitude;
+                amplitude *= self.gain;
+                frequency *= self.lacunarity;
+            }
+            sum / total
+        }
+    };
+}
+impl Fbm {
+    impl_fbm_sample!(sample2, Vec2);
+    impl_fbm_sample!(sample3, Vec3);
+    impl_fbm_sample!(sample4, Vec4);
+}
+
+//Domain warping: displaces p by noise sampled with the next seeds before sampling it
+pub fn warp2<F: Fn(Vec2<f32>, u32) -> f32>(
+    noise: F,
+    p: Vec2<f32>,
+    strength: f32,
+    seed: u32,
+) -> f32 {
+    let q = Vec2::new(
+        noise(p + WARP_OFFSET_1.truncate(), seed.wrapping_add(1)),
+        noise(p + WARP_OFFSET_2.truncate(), seed.wrapping_add(2)),
+    );
+    noise(p + q * strength, seed)
+}
+pub fn warp3<F: Fn(Vec3<f32>, u32) -> f32>(
+    noise: F,
+    p: Vec3<f32>,
+    strength: f32,
+    seed: u32,
+) -> f32 {
+    let q = Vec3::new(
+        noise(p + WARP_OFFSET_1, seed.wrapping_add(1)),
+        noise(p + WARP_OFFSET_2, seed.wrapping_add(2)),
+        noise(p + WARP_OFFSET_1 + WARP_OFFSET_2, seed.wrapping_add(3)),
+    );
+    noise(p + q * strength, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::noise::perlin::perlin3;
+    use crate::types::noise::simplex::simplex2;
+
+    #[test]
+    pub fn test_fbm() {
+        let p = Vec2::new(0.3, 0.8);
+        let single = Fbm {
+            octaves: 1,
+            frequency: 2.,
+            ..Fbm::default()
+        };
+        assert_eq!(single.sample2(simplex2, p, 3), simplex2(p * 2., 3));
+        let two = Fbm {
+            octaves: 2,
+            ..Fbm::default()
+        };
+        let expected = (simplex2(p, 3) + 0.5 * simplex2(p * 2., 4)) / 1.5;
+        assert!((two.sample2(simplex2, p, 3) - expected).abs() < 1e-6);
+        for i in 0..500 {
+            let q = Vec3::new(i as f32 * 0.17, 0.5, -(i as f32) * 0.05);
+            assert!(Fbm::default().sample3(perlin3, q, 0).abs() <= 1.05);
+        }
+    }
+
+    #[test]
+    pub fn test_warp() {
+        let p = Vec3::new(1.5, -0.2, 0.7);
+        assert_eq!(warp3(perlin3, p, 0., 1), perlin3(p, 1));
+        assert_ne!(warp3(perlin3, p, 2., 1), perlin3(p, 1));
+        let fbm = Fbm::default();
+        let q = Vec2::new(0.4, 0.9);
+        let warped = warp2(|x, seed| fbm.sample2(simplex2, x, seed), q, 1., 0);
+        assert!(warped.abs() <= 1.05);
+        assert_eq!(warp2(simplex2, q, 0., 5), simplex2(q, 5));
+    }
+}
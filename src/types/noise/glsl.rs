@@ -0,0 +1,259 @@
+//GLSL port of this module, function names and constants match the Rust ones
+pub const NOISE_GLSL: &str = include_str!("../../../shaders/noise.glsl");
+
+//Inserts the noise functions right after the #version line of a shader source
+pub fn with_noise(source: &str) -> String {
+    let (version, body) = match source.find("#version") {
+        Some(start) => {
+            let end = source[start..]
+                .find('\n')
+                .map_or(source.len(), |e| start + e + 1);
+            source.split_at(end)
+        }
+        None => ("", source),
+    };
+    let mut res = String::with_capacity(source.len() + NOISE_GLSL.len() + 1);
+    res.push_str(version);
+    if !version.is_empty() && !version.ends_with('\n') {
+        res.push('\n');
+    }
+    res.push_str(NOISE_GLSL);
+    res.push_str(body);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::noise::fractal::{WARP_OFFSET_1, WARP_OFFSET_2};
+    use crate::types::noise::hash::{HASH_MULTIPLIER_1, HASH_MULTIPLIER_2, VALUE_SCALE};
+    use crate::types::noise::perlin::{PERLIN2_SCALE, PERLIN3_SCALE, PERLIN4_SCALE};
+    use crate::types::noise::simplex::*;
+    use crate::types::noise::value::{value2, value3};
+
+    //Value of `const <type> <name> = <value>;` in the GLSL source
+    fn glsl_constant(name: &str) -> String {
+        NOISE_GLSL
+            .lines()
+            .filter(|line| line.starts_with("const "))
+            .find_map(|line| {
+                let mut parts = line.trim_end_matches(';').splitn(2, " = ");
+                let declaration = parts.next().unwrap();
+                if declaration.split_whitespace().last() == Some(name) {
+                    parts.next().map(|v| v.to_owned())
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| panic!("{} is not defined in noise.glsl", name))
+    }
+    fn glsl_float(name: &str) -> f32 {
+        glsl_constant(name).parse().unwrap()
+    }
+    fn glsl_vec3(name: &str) -> Vec<f32> {
+        glsl_constant(name)
+            .trim_start_matches("vec3(")
+            .trim_end_matches(')')
+            .split(", ")
+            .map(|c| c.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    pub fn test_glsl_constants_match() {
+        for &(name, value) in [
+            ("HASH_MULTIPLIER_1", HASH_MULTIPLIER_1),
+            ("HASH_MULTIPLIER_2", HASH_MULTIPLIER_2),
+        ]
+        .iter()
+        {
+            let glsl = glsl_constant(name);
+            let hex = glsl.trim_start_matches("0x").trim_end_matches('u');
+            assert_eq!(u32::from_str_radix(hex, 16).unwrap(), value, "{}", name);
+        }
+        for &(name, value) in [
+            ("VALUE_SCALE", VALUE_SCALE),
+            ("PERLIN2_SCALE", PERLIN2_SCALE),
+            ("PERLIN3_SCALE", PERLIN3_SCALE),
+            ("PERLIN4_SCALE", PERLIN4_SCALE),
+            ("SIMPLEX_F2", SIMPLEX_F2),
+            ("SIMPLEX_G2", SIMPLEX_G2),
+            ("SIMPLEX_F3", SIMPLEX_F3),
+            ("SIMPLEX_G3", SIMPLEX_G3),
+            ("SIMPLEX_F4", SIMPLEX_F4),
+            ("SIMPLEX_G4", SIMPLEX_G4),
+            ("SIMPLEX2_SCALE", SIMPLEX2_SCALE),
+            ("SIMPLEX3_SCALE", SIMPLEX3_SCALE),
+            ("SIMPLEX4_SCALE", SIMPLEX4_SCALE),
+        ]
+        .iter()
+        {
+            assert_eq!(glsl_float(name).to_bits(), value.to_bits(), "{}", name);
+        }
+        assert_eq!(
+            glsl_vec3("WARP_OFFSET_1"),
+            WARP_OFFSET_1.to_array().to_vec()
+        );
+        assert_eq!(
+            glsl_vec3("WARP_OFFSET_2"),
+            WARP_OFFSET_2.to_array().to_vec()
+        );
+    }
+
+    #[test]
+    pub fn test_glsl_functions_defined() {
+        for name in [
+            "uint hash(",
+            "uint hash2(",
+            "uint hash3(",
+            "uint hash4(",
+            "float to_signed_unit(",
+            "float fade(",
+            "float lerp(",
+            "float grad2(",
+            "float grad3(",
+            "float grad4(",
+            "float perlin2(",
+            "float perlin3(",
+            "float perlin4(",
+            "float simplex2(",
+            "float simplex3(",
+            "float simplex4(",
+            "float value2(",
+            "float value3(",
+        ]
+        .iter()
+        {
+            assert!(NOISE_GLSL.contains(name), "{}", name);
+        }
+        for noise in [
+            "perlin2", "perlin3", "perlin4", "simplex2", "simplex3", "simplex4", "value2", "value3",
+        ]
+        .iter()
+        {
+            assert!(NOISE_GLSL.contains(&format!("NOISE_FBM(fbm_{}, {},", noise, noise)));
+        }
+        for noise in [
+            "perlin2", "simplex2", "value2", "perlin3", "simplex3", "value3",
+        ]
+        .iter()
+        {
+            let dimension = &noise[noise.len() - 1..];
+            assert!(NOISE_GLSL.contains(&format!(
+                "NOISE_WARP{}(warp_{}, {})",
+                dimension, noise, noise
+            )));
+        }
+    }
+
+    #[test]
+    pub fn test_with_noise() {
+        let source = "#version 460 core\nout vec4 FragColor;\n";
+        let spliced = with_noise(source);
+        assert!(spliced.starts_with("#version 460 core\n"));
+        assert!(spliced.ends_with("out vec4 FragColor;\n"));
+        assert!(spliced.contains(NOISE_GLSL));
+        assert_eq!(
+            with_noise("void main() {}"),
+            format!("{}void main() {{}}", NOISE_GLSL)
+        );
+    }
+
+    //Samples every noise function on the GPU and compares with the Rust versions.
+    //Needs a display and an OpenGL 4.6 driver, run with cargo test -- --ignored
+    #[test]
+    #[ignore]
+    pub fn test_glsl_matches_rust() {
+        use crate::setup::quick_setup::initialize_hidden;
+        use crate::types::buffer::storage_buffer::SSBO;
+        use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+        use crate::types::noise::fractal::{warp2, warp3, Fbm};
+        use crate::types::noise::perlin::{perlin2, perlin3, perlin4};
+        use crate::types::shader::compute_program::{buffer_update_barrier, ComputeProgram};
+
+        const SEED: u32 = 7;
+        const OUTPUTS: usize = 11;
+        let _context = initialize_hidden();
+        let source = with_noise(&format!(
+            "#version 460 core
+layout(local_size_x = 64) in;
+layout(std430, binding = 0) readonly buffer Points {{ vec4 points[]; }};
+layout(std430, binding = 1) writeonly buffer Results {{ float results[]; }};
+void main() {{
+    uint id = gl_GlobalInvocationID.x;
+    if (id >= uint(points.length())) {{
+        return;
+    }}
+    vec4 p = points[id];
+    uint seed = {seed}u;
+    uint base = id * {outputs}u;
+    results[base] = perlin2(p.xy, seed);
+    results[base + 1u] = perlin3(p.xyz, seed);
+    results[base + 2u] = perlin4(p, seed);
+    results[base + 3u] = simplex2(p.xy, seed);
+    results[base + 4u] = simplex3(p.xyz, seed);
+    results[base + 5u] = simplex4(p, seed);
+    results[base + 6u] = value2(p.xy, seed);
+    results[base + 7u] = value3(p.xyz, seed);
+    results[base + 8u] = fbm_perlin3(p.xyz, seed, 5u, 1.0, 2.0, 0.5);
+    results[base + 9u] = warp_simplex2(p.xy, 0.8, seed);
+    results[base + 10u] = warp_value3(p.xyz, 0.8, seed);
+}}
+",
+            seed = SEED,
+            outputs = OUTPUTS
+        ));
+        let program = ComputeProgram::from_source(source).unwrap();
+
+        //Grid with negative coordinates, spacings that don't line up with the lattice
+        let points: Vec<[f32; 4]> = (0..1024)
+            .map(|i| {
+                [
+                    (i % 8) as f32 * 0.61 - 2.3,
+                    (i / 8 % 8) as f32 * 0.47 - 1.9,
+                    (i / 64 % 4) as f32 * 0.83 - 1.2,
+                    (i / 256) as f32 * 1.37 - 2.9,
+                ]
+            })
+            .collect();
+        let input = SSBO::from_data(&points, gl::STATIC_DRAW);
+        let output = SSBO::<f32>::zeroed(points.len() * OUTPUTS, gl::DYNAMIC_READ);
+        input.bind_base(0);
+        output.bind_base(1);
+        program.dispatch_invocations(points.len() as u32, 1, 1);
+        buffer_update_barrier();
+        let results = output.read();
+
+        let fbm = Fbm::default();
+        for (i, p) in points.iter().enumerate() {
+            let p2 = Vec2::new(p[0], p[1]);
+            let p3 = Vec3::new(p[0], p[1], p[2]);
+            let p4 = Vec4::new(p[0], p[1], p[2], p[3]);
+            let expected = [
+                perlin2(p2, SEED),
+                perlin3(p3, SEED),
+                perlin4(p4, SEED),
+                simplex2(p2, SEED),
+                simplex3(p3, SEED),
+                simplex4(p4, SEED),
+                value2(p2, SEED),
+                value3(p3, SEED),
+                fbm.sample3(perlin3, p3, SEED),
+                warp2(simplex2, p2, 0.8, SEED),
+                warp3(value3, p3, 0.8, SEED),
+            ];
+            //Drivers may fuse multiply-adds, so only agreement up to rounding is expected
+            for (k, &value) in expected.iter().enumerate() {
+                let gpu = results[i * OUTPUTS + k];
+                assert!(
+                    (gpu - value).abs() < 1e-4,
+                    "Output {} at {:?}: GLSL {} but Rust {}",
+                    k,
+                    p,
+                    gpu,
+                    value
+                );
+            }
+        }
+    }
+}
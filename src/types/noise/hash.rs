@@ -0,0 +1,54 @@
+//Integer hashing shared with shaders/noise.glsl, all arithmetic wraps like GLSL uint arithmetic
+pub const HASH_MULTIPLIER_1: u32 = 0x7feb_352d;
+pub const HASH_MULTIPLIER_2: u32 = 0x846c_a68b;
+//Maps the top 24 bits of a hash onto [-1, 1], 2 / (2^24 - 1)
+pub const VALUE_SCALE: f32 = 1.192093e-7;
+
+pub fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(HASH_MULTIPLIER_1);
+    x ^= x >> 15;
+    x = x.wrapping_mul(HASH_MULTIPLIER_2);
+    x ^= x >> 16;
+    x
+}
+//Lattice coordinates are reinterpreted as u32, like uint(ivec) in GLSL
+pub fn hash2(x: i32, y: i32, seed: u32) -> u32 {
+    hash(hash(hash(seed) ^ x as u32) ^ y as u32)
+}
+pub fn hash3(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    hash(hash2(x, y, seed) ^ z as u32)
+}
+pub fn hash4(x: i32, y: i32, z: i32, w: i32, seed: u32) -> u32 {
+    hash(hash3(x, y, z, seed) ^ w as u32)
+}
+pub fn to_signed_unit(h: u32) -> f32 {
+    (h >> 8) as f32 * VALUE_SCALE - 1.
+}
+//Quintic interpolation curve with zero first and second derivatives at 0 and 1
+pub fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+//Written out instead of using mix() in GLSL, which may round differently
+pub fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + t * (b - a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_hash() {
+        assert_eq!(hash(0), 0);
+        assert_eq!(hash(1), 0x6889_90c0);
+        assert_ne!(hash2(1, 2, 0), hash2(2, 1, 0));
+        assert_ne!(hash2(1, 2, 0), hash2(1, 2, 1));
+        assert_eq!(hash3(-1, 0, 0, 7), hash(hash(hash(hash(7) ^ u32::MAX))));
+        assert_eq!(to_signed_unit(0), -1.);
+        assert_eq!(to_signed_unit(u32::MAX), 1.);
+        assert_eq!(fade(0.), 0.);
+        assert_eq!(fade(0.5), 0.5);
+        assert_eq!(fade(1.), 1.);
+    }
+}
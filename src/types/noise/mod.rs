@@ -0,0 +1,6 @@
+pub mod fractal;
+pub mod glsl;
+pub mod hash;
+pub mod perlin;
+pub mod simplex;
+pub mod value;
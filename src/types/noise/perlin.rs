@@ -0,0 +1,152 @@
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+use crate::types::noise::hash::{fade, hash2, hash3, hash4, lerp};
+
+//Bring the results roughly into [-1, 1]
+pub const PERLIN2_SCALE: f32 = 0.507;
+pub const PERLIN3_SCALE: f32 = 0.936;
+pub const PERLIN4_SCALE: f32 = 0.87;
+
+//Gradient dot products, the low bits of the hash pick the gradient
+pub fn grad2(h: u32, x: f32, y: f32) -> f32 {
+    let h = h & 7;
+    let (u, v) = if h < 4 { (x, y) } else { (y, x) };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { 2. * v } else { -2. * v };
+    u + v
+}
+pub fn grad3(h: u32, x: f32, y: f32, z: f32) -> f32 {
+    let h = h & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+pub fn grad4(h: u32, x: f32, y: f32, z: f32, w: f32) -> f32 {
+    let h = h & 31;
+    let u = if h < 24 { x } else { y };
+    let v = if h < 16 { y } else { z };
+    let w = if h < 8 { z } else { w };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    let w = if h & 4 == 0 { w } else { -w };
+    u + v + w
+}
+
+pub fn perlin2(p: Vec2<f32>, seed: u32) -> f32 {
+    let (fx, fy) = (p.x.floor(), p.y.floor());
+    let (ix, iy) = (fx as i32, fy as i32);
+    let (x, y) = (p.x - fx, p.y - fy);
+    let (u, v) = (fade(x), fade(y));
+    let n00 = grad2(hash2(ix, iy, seed), x, y);
+    let n10 = grad2(hash2(ix.wrapping_add(1), iy, seed), x - 1., y);
+    let n01 = grad2(hash2(ix, iy.wrapping_add(1), seed), x, y - 1.);
+    let n11 = grad2(
+        hash2(ix.wrapping_add(1), iy.wrapping_add(1), seed),
+        x - 1.,
+        y - 1.,
+    );
+    PERLIN2_SCALE * lerp(v, lerp(u, n00, n10), lerp(u, n01, n11))
+}
+pub fn perlin3(p: Vec3<f32>, seed: u32) -> f32 {
+    let (fx, fy, fz) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (ix, iy, iz) = (fx as i32, fy as i32, fz as i32);
+    let (x, y, z) = (p.x - fx, p.y - fy, p.z - fz);
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        let h = hash3(
+            ix.wrapping_add(dx),
+            iy.wrapping_add(dy),
+            iz.wrapping_add(dz),
+            seed,
+        );
+        grad3(h, x - dx as f32, y - dy as f32, z - dz as f32)
+    };
+    let z0 = lerp(
+        v,
+        lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+        lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+    );
+    let z1 = lerp(
+        v,
+        lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+        lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+    );
+    PERLIN3_SCALE * lerp(w, z0, z1)
+}
+pub fn perlin4(p: Vec4<f32>, seed: u32) -> f32 {
+    let (fx, fy, fz, fw) = (p.x.floor(), p.y.floor(), p.z.floor(), p.w.floor());
+    let (ix, iy, iz, iw) = (fx as i32, fy as i32, fz as i32, fw as i32);
+    let (x, y, z, w) = (p.x - fx, p.y - fy, p.z - fz, p.w - fw);
+    let (fade_x, fade_y, fade_z, fade_w) = (fade(x), fade(y), fade(z), fade(w));
+    let corner = |dx: i32, dy: i32, dz: i32, dw: i32| {
+        let h = hash4(
+            ix.wrapping_add(dx),
+            iy.wrapping_add(dy),
+            iz.wrapping_add(dz),
+            iw.wrapping_add(dw),
+            seed,
+        );
+        grad4(
+            h,
+            x - dx as f32,
+            y - dy as f32,
+            z - dz as f32,
+            w - dw as f32,
+        )
+    };
+    let cube = |dw: i32| {
+        let z0 = lerp(
+            fade_y,
+            lerp(fade_x, corner(0, 0, 0, dw), corner(1, 0, 0, dw)),
+            lerp(fade_x, corner(0, 1, 0, dw), corner(1, 1, 0, dw)),
+        );
+        let z1 = lerp(
+            fade_y,
+            lerp(fade_x, corner(0, 0, 1, dw), corner(1, 0, 1, dw)),
+            lerp(fade_x, corner(0, 1, 1, dw), corner(1, 1, 1, dw)),
+        );
+        lerp(fade_z, z0, z1)
+    };
+    PERLIN4_SCALE * lerp(fade_w, cube(0), cube(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_perlin() {
+        //Gradient noise vanishes on the integer lattice
+        assert_eq!(perlin2(Vec2::new(3., -7.), 0), 0.);
+        assert_eq!(perlin3(Vec3::new(3., -7., 1.), 5), 0.);
+        assert_eq!(perlin4(Vec4::new(3., -7., 1., 0.), 5), 0.);
+        let mut max = 0f32;
+        for i in 0..4000 {
+            let t = i as f32 * 0.173;
+            let values = [
+                perlin2(Vec2::new(t, t * 0.37 - 20.), 1),
+                perlin3(Vec3::new(t, -t * 0.61, t * 0.29), 2),
+                perlin4(Vec4::new(t, t * 0.53, -t * 0.21, t * 0.77), 3),
+            ];
+            for &value in values.iter() {
+                assert!(value.abs() <= 1.05);
+                max = max.max(value.abs());
+            }
+        }
+        assert!(max > 0.5);
+        let p = Vec2::new(1.3, 2.7);
+        assert_ne!(perlin2(p, 0), perlin2(p, 1));
+        assert_eq!(perlin2(p, 4), perlin2(p, 4));
+        //Continuous across lattice cells
+        let below = perlin3(Vec3::new(0.999_99, 0.5, 0.5), 0);
+        let above = perlin3(Vec3::new(1.000_01, 0.5, 0.5), 0);
+        assert!((below - above).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+use crate::types::noise::hash::{hash2, hash3, hash4};
+use crate::types::noise::perlin::{grad2, grad3, grad4};
+
+//Skewing factors between the simplex grid and the cubic lattice, (sqrt(n + 1) - 1) / n and its inverse
+pub const SIMPLEX_F2: f32 = 0.366_025_42;
+pub const SIMPLEX_G2: f32 = 0.211_324_87;
+pub const SIMPLEX_F3: f32 = 0.333_333_34;
+pub const SIMPLEX_G3: f32 = 0.166_666_67;
+pub const SIMPLEX_F4: f32 = 0.309_017;
+pub const SIMPLEX_G4: f32 = 0.138_196_6;
+//Bring the results roughly into [-1, 1]
+pub const SIMPLEX2_SCALE: f32 = 40.;
+pub const SIMPLEX3_SCALE: f32 = 32.;
+pub const SIMPLEX4_SCALE: f32 = 27.;
+
+//Falloff of one simplex corner, radius is 0.5 in 2D and 0.6 above
+fn falloff(radius: f32, length_squared: f32, gradient: f32) -> f32 {
+    let t = radius - length_squared;
+    if t < 0. {
+        0.
+    } else {
+        let t = t * t;
+        t * t * gradient
+    }
+}
+
+pub fn simplex2(p: Vec2<f32>, seed: u32) -> f32 {
+    let s = (p.x + p.y) * SIMPLEX_F2;
+    let (i, j) = ((p.x + s).floor(), (p.y + s).floor());
+    let t = (i + j) * SIMPLEX_G2;
+    let (x0, y0) = (p.x - (i - t), p.y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let x1 = x0 - i1 as f32 + SIMPLEX_G2;
+    let y1 = y0 - j1 as f32 + SIMPLEX_G2;
+    let x2 = x0 - 1. + 2. * SIMPLEX_G2;
+    let y2 = y0 - 1. + 2. * SIMPLEX_G2;
+    let (ii, jj) = (i as i32, j as i32);
+    let h0 = hash2(ii, jj, seed);
+    let h1 = hash2(ii.wrapping_add(i1), jj.wrapping_add(j1), seed);
+    let h2 = hash2(ii.wrapping_add(1), jj.wrapping_add(1), seed);
+    let n0 = falloff(0.5, x0 * x0 + y0 * y0, grad2(h0, x0, y0));
+    let n1 = falloff(0.5, x1 * x1 + y1 * y1, grad2(h1, x1, y1));
+    let n2 = falloff(0.5, x2 * x2 + y2 * y2, grad2(h2, x2, y2));
+    SIMPLEX2_SCALE * (n0 + n1 + n2)
+}
+pub fn simplex3(p: Vec3<f32>, seed: u32) -> f32 {
+    let s = (p.x + p.y + p.z) * SIMPLEX_F3;
+    let (i, j, k) = ((p.x + s).floor(), (p.y + s).floor(), (p.z + s).floor());
+    let t = (i + j + k) * SIMPLEX_G3;
+    let (x0, y0, z0) = (p.x - (i - t), p.y - (j - t), p.z - (k - t));
+    //Second and third corner, walking along the largest coordinates first
+    let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+        if y0 >= z0 {
+            ((1, 0, 0), (1, 1, 0))
+        } else if x0 >= z0 {
+            ((1, 0, 0), (1, 0, 1))
+        } else {
+            ((0, 0, 1), (1, 0, 1))
+        }
+    } else if y0 < z0 {
+        ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+        ((0, 1, 0), (0, 1, 1))
+    } else {
+        ((0, 1, 0), (1, 1, 0))
+    };
+    let (ii, jj, kk) = (i as i32, j as i32, k as i32);
+    let corner = |di: i32, dj: i32, dk: i32, offset: f32| {
+        let x = x0 - di as f32 + offset;
+        let y = y0 - dj as f32 + offset;
+        let z = z0 - dk as f32 + offset;
+        let h = hash3(
+            ii.wrapping_add(di),
+            jj.wrapping_add(dj),
+            kk.wrapping_add(dk),
+            seed,
+        );
+        falloff(0.6, x * x + y * y + z * z, grad3(h, x, y, z))
+    };
+    let n0 = corner(0, 0, 0, 0.);
+    let n1 = corner(i1, j1, k1, SIMPLEX_G3);
+    let n2 = corner(i2, j2, k2, 2. * SIMPLEX_G3);
+    let n3 = corner(1, 1, 1, 3. * SIMPLEX_G3);
+    SIMPLEX3_SCALE * (n0 + n1 + n2 + n3)
+}
+pub fn simplex4(p: Vec4<f32>, seed: u32) -> f32 {
+    let s = (p.x + p.y + p.z + p.w) * SIMPLEX_F4;
+    let i = (p.x + s).floor();
+    let j = (p.y + s).floor();
+    let k = (p.z + s).floor();
+    let l = (p.w + s).floor();
+    let t = (i + j + k + l) * SIMPLEX_G4;
+    let x0 = p.x - (i - t);
+    let y0 = p.y - (j - t);
+    let z0 = p.z - (k - t);
+    let w0 = p.w - (l - t);
+    //Rank the coordinates by magnitude to find the simplex containing the point
+    let mut rank = [0; 4];
+    let coordinates = [x0, y0, z0, w0];
+    for a in 0..4 {
+        for b in a + 1..4 {
+            if coordinates[a] > coordinates[b] {
+                rank[a] += 1;
+            } else {
+                rank[b] += 1;
+            }
+        }
+    }
+    let step = |threshold: i32| {
+        let offset = |r: i32| if r >= threshold { 1 } else { 0 };
+        [
+            offset(rank[0]),
+            offset(rank[1]),
+            offset(rank[2]),
+            offset(rank[3]),
+        ]
+    };
+    let (ii, jj, kk, ll) = (i as i32, j as i32, k as i32, l as i32);
+    let corner = |d: [i32; 4], offset: f32| {
+        let x = x0 - d[0] as f32 + offset;
+        let y = y0 - d[1] as f32 + offset;
+        let z = z0 - d[2] as f32 + offset;
+        let w = w0 - d[3] as f32 + offset;
+        let h = hash4(
+            ii.wrapping_add(d[0]),
+            jj.wrapping_add(d[1]),
+            kk.wrapping_add(d[2]),
+            ll.wrapping_add(d[3]),
+            seed,
+        );
+        falloff(0.6, x * x + y * y + z * z + w * w, grad4(h, x, y, z, w))
+    };
+    let n0 = corner([0, 0, 0, 0], 0.);
+    let n1 = corner(step(3), SIMPLEX_G4);
+    let n2 = corner(step(2), 2. * SIMPLEX_G4);
+    let n3 = corner(step(1), 3. * SIMPLEX_G4);
+    let n4 = corner([1, 1, 1, 1], 4. * SIMPLEX_G4);
+    SIMPLEX4_SCALE * (n0 + n1 + n2 + n3 + n4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_simplex() {
+        let mut max = [0f32; 3];
+        for i in 0..4000 {
+            let t = i as f32 * 0.137;
+            let values = [
+                simplex2(Vec2::new(t, -t * 0.43 + 3.), 1),
+                simplex3(Vec3::new(t * 0.71, t * 0.33, -t), 2),
+                simplex4(Vec4::new(t, -t * 0.47, t * 0.19, t * 0.83), 3),
+            ];
+            for (m, &value) in max.iter_mut().zip(values.iter()) {
+                assert!(value.abs() <= 1.05);
+                *m = m.max(value.abs());
+            }
+        }
+        assert!(max.iter().all(|&m| m > 0.4));
+        let p = Vec3::new(0.3, 1.7, -2.2);
+        assert_ne!(simplex3(p, 0), simplex3(p, 1));
+        //Continuous across simplex boundaries
+        let x = Vec4::new(0.5, 0.5, 0.5, 0.5);
+        let dx = Vec4::new(1e-4, -1e-4, 2e-4, 0.);
+        assert!((simplex4(x, 0) - simplex4(x + dx, 0)).abs() < 1e-2);
+        assert!(
+            (simplex2(Vec2::new(0.5, 0.5), 0) - simplex2(Vec2::new(0.5, 0.500_1), 0)).abs() < 1e-2
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::types::linalg::vector::{Vec2, Vec3};
+use crate::types::noise::hash::{fade, hash2, hash3, lerp, to_signed_unit};
+
+//Interpolates random values in [-1, 1] placed on the integer lattice
+pub fn value2(p: Vec2<f32>, seed: u32) -> f32 {
+    let (fx, fy) = (p.x.floor(), p.y.floor());
+    let (ix, iy) = (fx as i32, fy as i32);
+    let (u, v) = (fade(p.x - fx), fade(p.y - fy));
+    let corner =
+        |dx: i32, dy: i32| to_signed_unit(hash2(ix.wrapping_add(dx), iy.wrapping_add(dy), seed));
+    lerp(
+        v,
+        lerp(u, corner(0, 0), corner(1, 0)),
+        lerp(u, corner(0, 1), corner(1, 1)),
+    )
+}
+pub fn value3(p: Vec3<f32>, seed: u32) -> f32 {
+    let (fx, fy, fz) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (ix, iy, iz) = (fx as i32, fy as i32, fz as i32);
+    let (u, v, w) = (fade(p.x - fx), fade(p.y - fy), fade(p.z - fz));
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        to_signed_unit(hash3(
+            ix.wrapping_add(dx),
+            iy.wrapping_add(dy),
+            iz.wrapping_add(dz),
+            seed,
+        ))
+    };
+    let z0 = lerp(
+        v,
+        lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+        lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+    );
+    let z1 = lerp(
+        v,
+        lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+        lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+    );
+    lerp(w, z0, z1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_value_noise() {
+        assert_eq!(
+            value2(Vec2::new(2., -3.), 9),
+            to_signed_unit(hash2(2, -3, 9))
+        );
+        assert_eq!(
+            value3(Vec3::new(2., -3., 4.), 9),
+            to_signed_unit(hash3(2, -3, 4, 9))
+        );
+        for i in 0..1000 {
+            let t = i as f32 * 0.31;
+            assert!(value2(Vec2::new(t, t * 0.7), 0).abs() <= 1.);
+            assert!(value3(Vec3::new(t, -t, t * 0.2), 0).abs() <= 1.);
+        }
+        let a = value2(Vec2::new(0.5, 0.5), 0);
+        let b = value2(Vec2::new(0.5, 0.500_1), 0);
+        assert!((a - b).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::types::data::data_layout::DataLayout;
+use crate::types::data::data_specification::ComponentType;
+use gl::types::*;
+use std::fmt;
+
+//How the shader sees an input, float for normalized and converted integers too
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScalarKind {
+    Float,
+    Int,
+    UnsignedInt,
+    Double,
+}
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AttributeType {
+    pub kind: ScalarKind,
+    pub components: GLint,
+}
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.kind {
+            ScalarKind::Float => "",
+            ScalarKind::Int => "i",
+            ScalarKind::UnsignedInt => "u",
+            ScalarKind::Double => "d",
+        };
+        if self.components == 1 {
+            let scalar = match self.kind {
+                ScalarKind::Float => "float",
+                ScalarKind::Int => "int",
+                ScalarKind::UnsignedInt => "uint",
+                ScalarKind::Double => "double",
+            };
+            write!(f, "{}", scalar)
+        } else {
+            write!(f, "{}vec{}", prefix, self.components)
+        }
+    }
+}
+
+//Vertex shader input as reported by glGetActiveAttrib
+#[derive(Clone, PartialEq, Debug)]
+pub struct ActiveAttribute {
+    pub name: String,
+    pub location: GLint,
+    pub gl_type: GLenum,
+    //Array length, 1 for plain attributes
+    pub size: GLint,
+}
+impl ActiveAttribute {
+    //Type of every location the attribute occupies and how many there are, matrices take one per column
+    pub fn location_type(&self) -> Option<(AttributeType, GLint)> {
+        let (kind, components, columns) = match self.gl_type {
+            gl::FLOAT => (ScalarKind::Float, 1, 1),
+            gl::FLOAT_VEC2 => (ScalarKind::Float, 2, 1),
+            gl::FLOAT_VEC3 => (ScalarKind::Float, 3, 1),
+            gl::FLOAT_VEC4 => (ScalarKind::Float, 4, 1),
+            gl::FLOAT_MAT2 => (ScalarKind::Float, 2, 2),
+            gl::FLOAT_MAT3 => (ScalarKind::Float, 3, 3),
+            gl::FLOAT_MAT4 => (ScalarKind::Float, 4, 4),
+            gl::FLOAT_MAT2x3 => (ScalarKind::Float, 3, 2),
+            gl::FLOAT_MAT2x4 => (ScalarKind::Float, 4, 2),
+            gl::FLOAT_MAT3x2 => (ScalarKind::Float, 2, 3),
+            gl::FLOAT_MAT3x4 => (ScalarKind::Float, 4, 3),
+            gl::FLOAT_MAT4x2 => (ScalarKind::Float, 2, 4),
+            gl::FLOAT_MAT4x3 => (ScalarKind::Float, 3, 4),
+            gl::INT => (ScalarKind::Int, 1, 1),
+            gl::INT_VEC2 => (ScalarKind::Int, 2, 1),
+            gl::INT_VEC3 => (ScalarKind::Int, 3, 1),
+            gl::INT_VEC4 => (ScalarKind::Int, 4, 1),
+            gl::UNSIGNED_INT => (ScalarKind::UnsignedInt, 1, 1),
+            gl::UNSIGNED_INT_VEC2 => (ScalarKind::UnsignedInt, 2, 1),
+            gl::UNSIGNED_INT_VEC3 => (ScalarKind::UnsignedInt, 3, 1),
+            gl::UNSIGNED_INT_VEC4 => (ScalarKind::UnsignedInt, 4, 1),
+            gl::DOUBLE => (ScalarKind::Double, 1, 1),
+            gl::DOUBLE_VEC2 => (ScalarKind::Double, 2, 1),
+            gl::DOUBLE_VEC3 => (ScalarKind::Double, 3, 1),
+            gl::DOUBLE_VEC4 => (ScalarKind::Double, 4, 1),
+            _ => return None,
+        };
+        Some((AttributeType { kind, components }, columns * self.size))
+    }
+}
+
+//What the shader receives for one attribute of a DataLayout
+pub fn layout_attribute_type(
+    component_type: ComponentType,
+    components: GLint,
+    integer: bool,
+) -> AttributeType {
+    let kind = if !integer {
+        ScalarKind::Float
+    } else {
+        match component_type {
+            ComponentType::Byte | ComponentType::Short | ComponentType::Int => ScalarKind::Int,
+            _ => ScalarKind::UnsignedInt,
+        }
+    };
+    AttributeType { kind, components }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum LayoutError {
+    //Shader input that no layout feeds, it reads a constant default value
+    Missing {
+        name: String,
+        location: GLuint,
+    },
+    //Layout attribute at a location the shader doesn't read, possibly optimized away by the linker
+    Unused {
+        location: GLuint,
+    },
+    //Different scalar kind, or more components than the shader reads.
+    //Fewer components are allowed, GL fills them up with (0, 0, 0, 1).
+    TypeMismatch {
+        name: String,
+        location: GLuint,
+        shader: AttributeType,
+        layout: AttributeType,
+    },
+}
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Missing { name, location } => write!(
+                f,
+                "Attribute {} at location {} is not provided by the layout",
+                name, location
+            ),
+            LayoutError::Unused { location } => write!(
+                f,
+                "Layout attribute at location {} is not read by the shader",
+                location
+            ),
+            LayoutError::TypeMismatch {
+                name,
+                location,
+                shader,
+                layout,
+            } => write!(
+                f,
+                "Attribute {} at location {} is declared as {} but the layout provides {}",
+                name, location, shader, layout
+            ),
+        }
+    }
+}
+
+//Matches the active attributes of a program against the layouts of all VBOs of a VAO
+pub fn validate_attributes(
+    attributes: &[ActiveAttribute],
+    layouts: &[&DataLayout],
+) -> Result<(), Vec<LayoutError>> {
+    let provided: Vec<(GLuint, AttributeType)> = layouts
+        .iter()
+        .flat_map(|layout| {
+            layout.specs().iter().enumerate().map(move |(i, spec)| {
+                let attribute_type =
+                    layout_attribute_type(spec.component_type, spec.components, spec.integer);
+                (layout.location(i), attribute_type)
+            })
+        })
+        .collect();
+    let mut errors = Vec::new();
+    let mut read = Vec::new();
+    //Built-ins like gl_VertexID have no location
+    for attribute in attributes.iter().filter(|a| a.location >= 0) {
+        let (shader, locations) = match attribute.location_type() {
+            Some(location_type) => location_type,
+            None => continue,
+        };
+        for location in attribute.location as GLuint..(attribute.location + locations) as GLuint {
+            read.push(location);
+            match provided.iter().find(|(l, _)| *l == location) {
+                None => errors.push(LayoutError::Missing {
+                    name: attribute.name.clone(),
+                    location,
+                }),
+                Some(&(_, layout)) => {
+                    //Double inputs would need glVertexAttribLPointer, which layouts don't use
+                    if layout.kind != shader.kind || layout.components > shader.components {
+                        errors.push(LayoutError::TypeMismatch {
+                            name: attribute.name.clone(),
+                            location,
+                            shader,
+                            layout,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    for &(location, _) in provided.iter() {
+        if !read.contains(&location) {
+            errors.push(LayoutError::Unused { location });
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::data::data_specification::DataSpecification;
+
+    fn attribute(name: &str, location: GLint, gl_type: GLenum) -> ActiveAttribute {
+        ActiveAttribute {
+            name: name.to_owned(),
+            location,
+            gl_type,
+            size: 1,
+        }
+    }
+
+    #[test]
+    pub fn test_validate_attributes() {
+        let attributes = vec![
+            attribute("aPos", 0, gl::FLOAT_VEC2),
+            attribute("aColor", 1, gl::FLOAT_VEC4),
+            attribute("gl_VertexID", -1, gl::INT),
+        ];
+        let fits = DataLayout::default()
+            .push(DataSpecification::float(2))
+            .push(DataSpecification::normalized(
+                ComponentType::UnsignedByte,
+                3,
+            ));
+        assert_eq!(validate_attributes(&attributes, &[&fits]), Ok(()));
+
+        //The Sierpinski mistake, vec3 positions into a vec2 input
+        let layout = DataLayout::default()
+            .push(DataSpecification::float(3))
+            .push(DataSpecification::integer(ComponentType::UnsignedByte, 4))
+            .push(DataSpecification::float(2));
+        let errors = validate_attributes(&attributes, &[&layout]).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[0],
+            LayoutError::TypeMismatch {
+                name: "aPos".to_owned(),
+                location: 0,
+                shader: AttributeType {
+                    kind: ScalarKind::Float,
+                    components: 2
+                },
+                layout: AttributeType {
+                    kind: ScalarKind::Float,
+                    components: 3
+                },
+            }
+        );
+        assert_eq!(
+            format!("{}", errors[1]),
+            "Attribute aColor at location 1 is declared as vec4 but the layout provides uvec4"
+        );
+        assert_eq!(errors[2], LayoutError::Unused { location: 2 });
+    }
+
+    #[test]
+    pub fn test_validate_instanced_matrix() {
+        let attributes = vec![
+            attribute("aPos", 0, gl::FLOAT_VEC3),
+            attribute("aModel", 1, gl::FLOAT_MAT4),
+        ];
+        let vertices = DataLayout::default().push(DataSpecification::float(3));
+        let mut instances = DataLayout::default();
+        for column in 0..3 {
+            instances = instances.push(DataSpecification::float(4).with_location(1 + column));
+        }
+        let errors =
+            validate_attributes(&attributes, &[&vertices, &instances.clone()]).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![LayoutError::Missing {
+                name: "aModel".to_owned(),
+                location: 4
+            }]
+        );
+        let instances = instances.push(DataSpecification::float(4)).with_divisor(1);
+        assert_eq!(
+            validate_attributes(&attributes, &[&vertices, &instances]),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::types::shader::shader::Shader;
+use crate::types::shader::shader_program::ShaderProgram;
+use gl::types::*;
+
+//A program made of a single compute shader, uniforms are set through program
+pub struct ComputeProgram {
+    pub program: ShaderProgram,
+    //local_size_x, local_size_y and local_size_z of the shader
+    pub work_group_size: [GLuint; 3],
+}
+impl ComputeProgram {
+    pub fn from_source(source: String) -> Result<Self, String> {
+        let shader = Shader::from_source(source, gl::COMPUTE_SHADER)?;
+        let program = ShaderProgram::link(&[&shader])?;
+        let mut size = [0; 3];
+        unsafe { gl::GetProgramiv(program.id, gl::COMPUTE_WORK_GROUP_SIZE, size.as_mut_ptr()) }
+        Ok(ComputeProgram {
+            program,
+            work_group_size: [size[0] as GLuint, size[1] as GLuint, size[2] as GLuint],
+        })
+    }
+    pub fn gl_use(&self) {
+        self.program.gl_use()
+    }
+    //Number of work groups in each dimension, uses the program
+    pub fn dispatch(&self, x: GLuint, y: GLuint, z: GLuint) {
+        debug_assert!(x > 0 && y > 0 && z > 0);
+        self.gl_use();
+        unsafe { gl::DispatchCompute(x, y, z) }
+    }
+    //Enough work groups to cover width * height * depth invocations, the shader has to skip the excess
+    pub fn dispatch_invocations(&self, width: GLuint, height: GLuint, depth: GLuint) {
+        let groups = |count: GLuint, size: GLuint| count.div_ceil(size).max(1);
+        self.dispatch(
+            groups(width, self.work_group_size[0]),
+            groups(height, self.work_group_size[1]),
+            groups(depth, self.work_group_size[2]),
+        )
+    }
+}
+
+//Makes writes of earlier dispatches visible to the operations in barriers,
+//e.g. gl::SHADER_STORAGE_BARRIER_BIT before the next dispatch reads the results
+pub fn memory_barrier(barriers: GLbitfield) {
+    unsafe { gl::MemoryBarrier(barriers) }
+}
+//Before drawing with a vertex buffer that was written by a compute shader
+pub fn vertex_attrib_barrier() {
+    memory_barrier(gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT)
+}
+//Before reading a storage buffer back with glGetBufferSubData or a mapping
+pub fn buffer_update_barrier() {
+    memory_barrier(gl::BUFFER_UPDATE_BARRIER_BIT)
+}
+//Between dispatches that read and write the same storage buffers
+pub fn storage_barrier() {
+    memory_barrier(gl::SHADER_STORAGE_BARRIER_BIT)
+}
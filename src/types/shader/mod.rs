@@ -1,3 +1,5 @@
+pub mod attribute;
+pub mod compute_program;
 pub mod shader;
 pub mod shader_program;
 pub mod texture;
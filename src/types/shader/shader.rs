@@ -7,7 +7,15 @@ pub struct Shader {
 }
 impl Shader {
     pub fn from_source(source: String, kind: GLenum) -> Result<Self, String> {
-        debug_assert!([gl::VERTEX_SHADER, gl::FRAGMENT_SHADER].contains(&kind));
+        debug_assert!([
+            gl::VERTEX_SHADER,
+            gl::TESS_CONTROL_SHADER,
+            gl::TESS_EVALUATION_SHADER,
+            gl::GEOMETRY_SHADER,
+            gl::FRAGMENT_SHADER,
+            gl::COMPUTE_SHADER
+        ]
+        .contains(&kind));
         let source = CString::new(source).unwrap();
         unsafe {
             let id = gl::CreateShader(kind);
@@ -1,5 +1,11 @@
+use crate::types::buffer::draw_mode::DrawMode;
+use crate::types::data::data_layout::DataLayout;
 use crate::types::linalg::dimension::Dimension;
+use crate::types::linalg::fixed_matrix::{Mat3, Mat4};
 use crate::types::linalg::matrix::Matrix;
+use crate::types::linalg::storage_order::StorageOrder;
+use crate::types::linalg::vector::{Vec2, Vec3, Vec4};
+use crate::types::shader::attribute::{validate_attributes, ActiveAttribute, LayoutError};
 use crate::types::shader::shader::Shader;
 use crate::types::shader::uniform::Uniform;
 use gl::types::*;
@@ -7,9 +13,42 @@ use std::ffi::CString;
 
 pub struct ShaderProgram {
     pub id: GLuint,
+    attributes: Vec<ActiveAttribute>,
+    stages: Vec<GLenum>,
+    //Input primitive of the geometry shader if it reads straight from the vertex shader
+    geometry_input: Option<GLenum>,
 }
+
+//Checks that the shader kinds form a complete pipeline before handing them to the linker
+pub fn validate_stages(kinds: &[GLenum]) -> Result<(), String> {
+    let has = |kind: GLenum| kinds.contains(&kind);
+    if kinds.is_empty() {
+        return Err("A program needs at least one shader".to_owned());
+    }
+    if has(gl::COMPUTE_SHADER) {
+        if kinds.iter().any(|&k| k != gl::COMPUTE_SHADER) {
+            return Err("Compute shaders can't be linked with other stages".to_owned());
+        }
+        return Ok(());
+    }
+    if !has(gl::VERTEX_SHADER) {
+        return Err("A graphics program needs a vertex shader".to_owned());
+    }
+    //Without a control shader the levels come from glPatchParameterfv
+    if has(gl::TESS_CONTROL_SHADER) && !has(gl::TESS_EVALUATION_SHADER) {
+        return Err(
+            "A tessellation control shader needs a tessellation evaluation shader".to_owned(),
+        );
+    }
+    Ok(())
+}
+
 impl ShaderProgram {
     pub fn link(shaders: &[&Shader]) -> Result<Self, String> {
+        let mut stages: Vec<GLenum> = shaders.iter().map(|shader| shader.kind).collect();
+        validate_stages(&stages)?;
+        stages.sort_unstable();
+        stages.dedup();
         unsafe {
             let id = gl::CreateProgram();
             for shader in shaders.iter() {
@@ -28,10 +67,93 @@ impl ShaderProgram {
                 gl::GetProgramInfoLog(id, len, std::ptr::null_mut(), buffer.as_ptr() as *mut i8);
                 Err(CString::from_vec_unchecked(buffer).into_string().unwrap())
             } else {
-                Ok(ShaderProgram { id })
+                let geometry_input = if stages.contains(&gl::GEOMETRY_SHADER)
+                    && !stages.contains(&gl::TESS_EVALUATION_SHADER)
+                {
+                    let mut input = 0;
+                    gl::GetProgramiv(id, gl::GEOMETRY_INPUT_TYPE, &mut input);
+                    Some(input as GLenum)
+                } else {
+                    None
+                };
+                Ok(ShaderProgram {
+                    id,
+                    attributes: ShaderProgram::query_attributes(id),
+                    stages,
+                    geometry_input,
+                })
             }
         }
     }
+    fn query_attributes(id: GLuint) -> Vec<ActiveAttribute> {
+        let (mut count, mut max_length) = (0, 0);
+        unsafe {
+            gl::GetProgramiv(id, gl::ACTIVE_ATTRIBUTES, &mut count);
+            gl::GetProgramiv(id, gl::ACTIVE_ATTRIBUTE_MAX_LENGTH, &mut max_length);
+        }
+        (0..count as GLuint)
+            .map(|index| {
+                let mut buffer = vec![0u8; max_length.max(1) as usize];
+                let (mut length, mut size, mut gl_type) = (0, 0, 0);
+                unsafe {
+                    gl::GetActiveAttrib(
+                        id,
+                        index,
+                        max_length,
+                        &mut length,
+                        &mut size,
+                        &mut gl_type,
+                        buffer.as_mut_ptr() as *mut GLchar,
+                    );
+                }
+                buffer.truncate(length as usize);
+                let name = String::from_utf8(buffer).unwrap();
+                let cstr = CString::new(name.as_str()).unwrap();
+                let location = unsafe { gl::GetAttribLocation(id, cstr.as_ptr()) };
+                ActiveAttribute {
+                    name,
+                    location,
+                    gl_type,
+                    size,
+                }
+            })
+            .collect()
+    }
+    pub fn has_stage(&self, kind: GLenum) -> bool {
+        self.stages.contains(&kind)
+    }
+    //Patches are required by and only allowed with tessellation, geometry shaders fix the primitive
+    pub fn validate_draw_mode(&self, mode: DrawMode) -> Result<(), String> {
+        let tessellation = self.has_stage(gl::TESS_EVALUATION_SHADER);
+        match mode {
+            DrawMode::Patches(_) if !tessellation => {
+                Err("Patches can only be drawn with tessellation shaders".to_owned())
+            }
+            DrawMode::Patches(_) => Ok(()),
+            _ if tessellation => Err(format!(
+                "Programs with tessellation shaders have to draw patches, not {:?}",
+                mode
+            )),
+            _ => match self.geometry_input {
+                Some(input) if mode.geometry_input() != Some(input) => Err(format!(
+                    "{:?} doesn't match the input primitive of the geometry shader",
+                    mode
+                )),
+                _ => Ok(()),
+            },
+        }
+    }
+    //Vertex shader inputs, queried once after linking
+    pub fn attributes(&self) -> &[ActiveAttribute] {
+        &self.attributes
+    }
+    pub fn validate_layout(&self, layout: &DataLayout) -> Result<(), Vec<LayoutError>> {
+        validate_attributes(&self.attributes, &[layout])
+    }
+    //For VAOs with several VBOs, see VAOBuilder::add_vbo
+    pub fn validate_layouts(&self, layouts: &[&DataLayout]) -> Result<(), Vec<LayoutError>> {
+        validate_attributes(&self.attributes, layouts)
+    }
 
     pub fn gl_use(&self) {
         unsafe { gl::UseProgram(self.id) }
@@ -50,6 +172,47 @@ impl ShaderProgram {
             Ok(Uniform { id })
         }
     }
+    pub fn uniform_block_index(&self, name: &str) -> Result<GLuint, String> {
+        let cstr = CString::new(name).unwrap();
+        let index = unsafe { gl::GetUniformBlockIndex(self.id, cstr.as_ptr()) };
+        if index == gl::INVALID_INDEX {
+            Err(format!("Uniform block {} not found!", name))
+        } else {
+            Ok(index)
+        }
+    }
+    //Reads the block from the UniformBuffer bound to binding_point, see UniformBuffer::bind_base
+    pub fn bind_uniform_block(&self, name: &str, binding_point: GLuint) -> Result<(), String> {
+        let index = self.uniform_block_index(name)?;
+        unsafe { gl::UniformBlockBinding(self.id, index, binding_point) }
+        Ok(())
+    }
+    //Size in bytes the shader expects, to check it against BlockBuffer::as_bytes
+    pub fn uniform_block_size(&self, name: &str) -> Result<usize, String> {
+        let index = self.uniform_block_index(name)?;
+        let mut size = 0;
+        unsafe {
+            gl::GetActiveUniformBlockiv(self.id, index, gl::UNIFORM_BLOCK_DATA_SIZE, &mut size)
+        }
+        Ok(size as usize)
+    }
+    pub fn storage_block_index(&self, name: &str) -> Result<GLuint, String> {
+        let cstr = CString::new(name).unwrap();
+        let index = unsafe {
+            gl::GetProgramResourceIndex(self.id, gl::SHADER_STORAGE_BLOCK, cstr.as_ptr())
+        };
+        if index == gl::INVALID_INDEX {
+            Err(format!("Storage block {} not found!", name))
+        } else {
+            Ok(index)
+        }
+    }
+    //Reads and writes the SSBO bound to binding_point, see Buffer::bind_base
+    pub fn bind_storage_block(&self, name: &str, binding_point: GLuint) -> Result<(), String> {
+        let index = self.storage_block_index(name)?;
+        unsafe { gl::ShaderStorageBlockBinding(self.id, index, binding_point) }
+        Ok(())
+    }
     pub fn uniform1i(&self, uniform: &Uniform, i1: i32) {
         //TODO Design decision: Make sure shader program is active? Requires internal "active" field and mutability
         unsafe { gl::Uniform1i(uniform.id, i1) }
@@ -62,9 +225,26 @@ impl ShaderProgram {
         //TODO Design decision: Make sure shader program is active? Requires internal "active" field and mutability.
         unsafe { gl::Uniform4f(uniform.id, f1, f2, f3, f4) }
     }
-    pub fn uniform_matrix4fv(&self, uniform: &Uniform, mat: &Matrix<f32>) {
+    pub fn uniform_matrix4fv<O: StorageOrder>(&self, uniform: &Uniform, mat: &Matrix<f32, O>) {
         //TODO Design decision: Make sure shader program is active? Requires internal "active" field and mutability.
         debug_assert!(mat.dimension == Dimension::new(4, 4));
+        //OpenGL expects column-major data
+        let transpose = if O::ROW_MAJOR { gl::TRUE } else { gl::FALSE };
+        unsafe { gl::UniformMatrix4fv(uniform.id, 1, transpose, mat.as_ptr()) }
+    }
+    pub fn uniform_vec2(&self, uniform: &Uniform, vec: &Vec2<f32>) {
+        unsafe { gl::Uniform2fv(uniform.id, 1, vec.as_ptr()) }
+    }
+    pub fn uniform_vec3(&self, uniform: &Uniform, vec: &Vec3<f32>) {
+        unsafe { gl::Uniform3fv(uniform.id, 1, vec.as_ptr()) }
+    }
+    pub fn uniform_vec4(&self, uniform: &Uniform, vec: &Vec4<f32>) {
+        unsafe { gl::Uniform4fv(uniform.id, 1, vec.as_ptr()) }
+    }
+    pub fn uniform_mat3(&self, uniform: &Uniform, mat: &Mat3<f32>) {
+        unsafe { gl::UniformMatrix3fv(uniform.id, 1, gl::TRUE, mat.as_ptr()) }
+    }
+    pub fn uniform_mat4(&self, uniform: &Uniform, mat: &Mat4<f32>) {
         unsafe { gl::UniformMatrix4fv(uniform.id, 1, gl::TRUE, mat.as_ptr()) }
     }
 }
@@ -73,3 +253,42 @@ impl Drop for ShaderProgram {
         unsafe { gl::DeleteProgram(self.id) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_validate_stages() {
+        assert!(validate_stages(&[gl::VERTEX_SHADER, gl::FRAGMENT_SHADER]).is_ok());
+        assert!(validate_stages(&[gl::COMPUTE_SHADER]).is_ok());
+        assert!(validate_stages(&[
+            gl::VERTEX_SHADER,
+            gl::TESS_CONTROL_SHADER,
+            gl::TESS_EVALUATION_SHADER,
+            gl::GEOMETRY_SHADER,
+            gl::FRAGMENT_SHADER
+        ])
+        .is_ok());
+        assert!(validate_stages(&[
+            gl::VERTEX_SHADER,
+            gl::TESS_EVALUATION_SHADER,
+            gl::FRAGMENT_SHADER
+        ])
+        .is_ok());
+        assert!(validate_stages(&[]).is_err());
+        assert!(validate_stages(&[gl::COMPUTE_SHADER, gl::FRAGMENT_SHADER]).is_err());
+        assert!(validate_stages(&[gl::GEOMETRY_SHADER, gl::FRAGMENT_SHADER]).is_err());
+        assert!(validate_stages(&[
+            gl::VERTEX_SHADER,
+            gl::TESS_CONTROL_SHADER,
+            gl::FRAGMENT_SHADER
+        ])
+        .is_err());
+        assert_eq!(
+            DrawMode::TriangleStrip.geometry_input(),
+            Some(gl::TRIANGLES)
+        );
+        assert_eq!(DrawMode::Patches(3).gl_mode(), gl::PATCHES);
+    }
+}
@@ -0,0 +1,254 @@
+//#[derive(Vertex)] for #[repr(C)] structs, generates lib::types::data::vertex::Vertex.
+//Fields accept #[vertex(location = n, divisor = n, normalize, integer)] and #[vertex(skip)].
+//Locations have to be unique, fields without one continue after the largest location so far.
+//Structs with padding bytes are rejected at compile time, fill the gaps with skipped fields.
+//Also implements lib::types::data::packing::Pod, which every field has to implement.
+//Parses the token stream by hand to stay free of dependencies.
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+
+struct Field {
+    name: String,
+    ty: String,
+    location: Option<u32>,
+    divisor: u32,
+    normalize: bool,
+    integer: bool,
+    skip: bool,
+}
+
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut repr_c = false;
+    let mut name = None;
+    let mut body = None;
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(g)) = tokens.get(i + 1) {
+                    let attribute = g.stream().to_string().replace(' ', "");
+                    repr_c |= attribute.starts_with("repr(") && attribute.contains('C');
+                }
+                i += 1;
+            }
+            TokenTree::Ident(ident) if ident.to_string() == "struct" => {
+                name = match tokens.get(i + 1) {
+                    Some(TokenTree::Ident(n)) => Some(n.to_string()),
+                    _ => None,
+                };
+                body = match tokens.get(i + 2) {
+                    Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                        Some(g.clone())
+                    }
+                    Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+                        return Err("Vertex can't be derived for generic structs".to_owned())
+                    }
+                    _ => {
+                        return Err(
+                            "Vertex can only be derived for structs with named fields".to_owned()
+                        )
+                    }
+                };
+                break;
+            }
+            TokenTree::Ident(ident)
+                if ident.to_string() == "enum" || ident.to_string() == "union" =>
+            {
+                return Err("Vertex can only be derived for structs".to_owned());
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    let (name, body) = match (name, body) {
+        (Some(name), Some(body)) => (name, body),
+        _ => return Err("Vertex can only be derived for structs".to_owned()),
+    };
+    if !repr_c {
+        return Err("Vertex requires #[repr(C)] so that field offsets are stable".to_owned());
+    }
+    let fields = parse_fields(&body)?;
+
+    let mut pushes = String::new();
+    //Like DataLayout::push, attributes without a location take the one after the largest so far
+    let mut locations: Vec<u32> = Vec::new();
+    for field in fields.iter().filter(|f| !f.skip) {
+        let location = field
+            .location
+            .unwrap_or_else(|| locations.iter().max().map_or(0, |l| l + 1));
+        if locations.contains(&location) {
+            return Err(format!(
+                "Location {} is used by more than one attribute of {}",
+                location, name
+            ));
+        }
+        locations.push(location);
+        pushes.push_str(&format!(
+            ".push_at(<{ty} as ::lib::types::data::vertex::VertexAttribute>::specification({normalize}, {integer}).with_divisor({divisor}), \
+             {location}, \
+             unsafe {{ ::std::ptr::addr_of!((*base).{name}) as usize - base as usize }} as u32)",
+            ty = field.ty,
+            normalize = field.normalize,
+            integer = field.integer,
+            divisor = field.divisor,
+            location = location,
+            name = field.name,
+        ));
+    }
+    //Padding would be uninitialized bytes in every upload
+    let field_sizes: String = fields
+        .iter()
+        .map(|f| format!(" + ::std::mem::size_of::<{}>()", f.ty))
+        .collect();
+    let pod_checks: String = fields
+        .iter()
+        .map(|f| format!("field_is_pod::<{}>();", f.ty))
+        .collect();
+    Ok(format!(
+        "const _: () = ::std::assert!(
+            ::std::mem::size_of::<{name}>() == 0{field_sizes},
+            \"{name} has padding bytes, add explicit #[vertex(skip)] padding fields\"
+        );
+        const _: fn() = || {{
+            fn field_is_pod<T: ::lib::types::data::packing::Pod>() {{}}
+            {pod_checks}
+        }};
+        unsafe impl ::lib::types::data::packing::Pod for {name} {{}}
+        impl ::lib::types::data::vertex::Vertex for {name} {{
+            fn data_layout() -> ::lib::types::data::data_layout::DataLayout {{
+                let uninit = ::std::mem::MaybeUninit::<{name}>::uninit();
+                let base = uninit.as_ptr();
+                ::lib::types::data::data_layout::DataLayout::default(){pushes}
+                    .with_stride(::std::mem::size_of::<{name}>() as u32)
+            }}
+        }}",
+        name = name,
+        field_sizes = field_sizes,
+        pod_checks = pod_checks,
+        pushes = pushes
+    ))
+}
+
+fn parse_fields(body: &Group) -> Result<Vec<Field>, String> {
+    let mut fields = Vec::new();
+    let tokens: Vec<TokenTree> = body.stream().into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        let mut field = Field {
+            name: String::new(),
+            ty: String::new(),
+            location: None,
+            divisor: 0,
+            normalize: false,
+            integer: false,
+            skip: false,
+        };
+        //Attributes
+        while let Some(TokenTree::Punct(p)) = tokens.get(i) {
+            if p.as_char() != '#' {
+                break;
+            }
+            if let Some(TokenTree::Group(g)) = tokens.get(i + 1) {
+                parse_attribute(g, &mut field)?;
+            }
+            i += 2;
+        }
+        //Visibility, pub or pub(...)
+        if let Some(TokenTree::Ident(ident)) = tokens.get(i) {
+            if ident.to_string() == "pub" {
+                i += 1;
+                if let Some(TokenTree::Group(g)) = tokens.get(i) {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        i += 1;
+                    }
+                }
+            }
+        }
+        field.name = match tokens.get(i) {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("Expected a field name".to_owned()),
+        };
+        i += 2;
+        //The type ends at the next comma outside of angle brackets
+        let mut depth = 0;
+        let mut ty = Vec::new();
+        while let Some(token) = tokens.get(i) {
+            if let TokenTree::Punct(p) = token {
+                match p.as_char() {
+                    '<' => depth += 1,
+                    '>' => depth -= 1,
+                    ',' if depth == 0 => break,
+                    _ => {}
+                }
+            }
+            ty.push(token.clone());
+            i += 1;
+        }
+        field.ty = ty.into_iter().collect::<TokenStream>().to_string();
+        fields.push(field);
+        i += 1;
+    }
+    Ok(fields)
+}
+
+fn parse_attribute(attribute: &Group, field: &mut Field) -> Result<(), String> {
+    let mut tokens = attribute.stream().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "vertex" => {}
+        _ => return Ok(()),
+    }
+    let arguments = match tokens.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => g.stream(),
+        _ => return Err("Expected #[vertex(...)]".to_owned()),
+    };
+    let arguments: Vec<TokenTree> = arguments.into_iter().collect();
+    let mut i = 0;
+    while i < arguments.len() {
+        match &arguments[i] {
+            TokenTree::Ident(ident) => match ident.to_string().as_str() {
+                "normalize" => field.normalize = true,
+                "integer" => field.integer = true,
+                "skip" => field.skip = true,
+                "location" => {
+                    field.location = Some(parse_value(&arguments, i, "location")?);
+                    i += 2;
+                }
+                "divisor" => {
+                    field.divisor = parse_value(&arguments, i, "divisor")?;
+                    i += 2;
+                }
+                other => return Err(format!("Unknown vertex attribute option {}", other)),
+            },
+            TokenTree::Punct(p) if p.as_char() == ',' => {}
+            other => return Err(format!("Unexpected token {} in #[vertex(...)]", other)),
+        }
+        i += 1;
+    }
+    if field.normalize && field.integer {
+        return Err("An attribute can't be both normalized and integer".to_owned());
+    }
+    Ok(())
+}
+
+//The integer in `option = n`, with the option name at arguments[i]
+fn parse_value(arguments: &[TokenTree], i: usize, option: &str) -> Result<u32, String> {
+    let value = match (arguments.get(i + 1), arguments.get(i + 2)) {
+        (Some(TokenTree::Punct(p)), Some(TokenTree::Literal(l))) if p.as_char() == '=' => {
+            l.to_string()
+        }
+        _ => return Err(format!("Expected {} = <integer>", option)),
+    };
+    value
+        .parse()
+        .map_err(|_| format!("Invalid attribute {} {}", option, value))
+}